@@ -3,8 +3,23 @@
 use {
     clap::Parser,
     clap_cargo::style::CLAP_STYLING,
-    dtg_lib::{Dtg, Format, tz},
-    jiff::tz::TimeZone,
+    dtg_lib::{
+        ClockGlyphs, Date, Dtg, DtgError, DtgErrorKind, Format, FormatRegistry, MeetingSlot,
+        WorkingHours, XAlphabet, ZoneInfo, ZoneTransition, tz, tz_abbreviations, tz_alias_set,
+        tz_db_set, tz_db_version, tz_split,
+    },
+    jiff::{
+        SignedDuration, Timestamp,
+        tz::{Offset, TimeZone},
+    },
+    std::{
+        collections::BTreeMap,
+        fmt::Write,
+        io::{BufRead, IsTerminal},
+        path::PathBuf,
+    },
+    toml_edit::{DocumentMut, Item, TableLike, Value},
+    unicode_width::UnicodeWidthStr,
 };
 
 #[cfg(unix)]
@@ -15,6 +30,47 @@ fn error(code: i32, msg: &str) {
     std::process::exit(code);
 }
 
+/// Print a library error and exit with the exit code for its kind
+fn error_(e: &DtgError) {
+    error(exit_code(e), &message(e));
+}
+
+/// Exit code for a library error
+fn exit_code(e: &DtgError) -> i32 {
+    match e.kind() {
+        DtgErrorKind::InvalidTimestamp { .. }
+        | DtgErrorKind::InvalidXChar { .. }
+        | DtgErrorKind::InvalidBcdChar { .. }
+        | DtgErrorKind::InvalidBcdValue { .. }
+        | DtgErrorKind::InvalidDuration { .. }
+        | DtgErrorKind::InvalidInterval { .. }
+        | DtgErrorKind::InvalidMilitaryZone { .. } => 2,
+        DtgErrorKind::InvalidTimezone { .. }
+        | DtgErrorKind::InvalidTzif { .. }
+        | DtgErrorKind::AmbiguousAbbreviation { .. } => 3,
+        DtgErrorKind::LocalTimezone => 5,
+        DtgErrorKind::InvalidXAlphabet { .. } => 7,
+        DtgErrorKind::InvalidTimezoneDatabase { .. } => 8,
+        DtgErrorKind::InvalidTimezoneAlias { .. } => 9,
+        DtgErrorKind::InvalidWorkingHours { .. } => 10,
+        DtgErrorKind::InvalidNamedFormat { .. }
+        | DtgErrorKind::InvalidFormat { .. }
+        | DtgErrorKind::InvalidFormatSpecifier { .. } => 11,
+        _ => 1,
+    }
+}
+
+/// Error message followed by its sources
+fn message(e: &DtgError) -> String {
+    let mut r = e.to_string();
+    let mut source = std::error::Error::source(e);
+    while let Some(e) = source {
+        write!(r, ": {e}").unwrap();
+        source = e.source();
+    }
+    r
+}
+
 #[derive(Parser)]
 #[command(
     about = "\
@@ -24,7 +80,7 @@ Date/time CLI utility
 
 ---\
     ",
-    version,
+    disable_version_flag = true,
     max_term_width = 80,
     styles = CLAP_STYLING,
     after_help = "\
@@ -61,17 +117,66 @@ Notes:
    Minute | 0-59             | 0-x
    Second | 0-59             | 0-x
 
+   Negative years have a `-` prefix. The extended \"x\" format (`-n
+   x-extended`) uses the specified timezone and appends any base 60 fractional
+   seconds after a `.` and any offset as `+`/`-` base 60 hours, minutes, and
+   seconds (if not zero); `-X` accepts both, as well as intervals
+   (`START/END` or `START/+DURATION`, where `DURATION` is `[-]SECONDS` in base
+   60 with an optional `.FRACTION`), which print as `START/END` in each format.
+
+   The `--x-alphabet` option replaces the digits above with \"url-safe\" (base
+   64: `0-9A-Z_a-z~`), \"dns\" (base 36: `0-9a-z`, case-insensitive; a DNS
+   label only without a negative year, fraction, or offset), or a custom
+   alphabet of 2-64 unique characters; fields that don't fit in one digit use a
+   fixed number of digits.
+
 3. Prints the timestamp in each format with one or more timezones using a
-   comma-separated string (`-z UTC,EST`).
+   comma-separated string (`-z UTC,EST`). Timezones are IANA names, military
+   time zone letters or names (`Z`/`Zulu`, `A`/`Alpha` ... `Y`/`Yankee`, and
+   `J`/`Juliet` for local), fixed UTC offsets (`+05:30`, `-0800`, `UTC+3`,
+   `GMT-4`; east of UTC if `+`), abbreviations in use from 2020 to 2030 (`IST`,
+   `AEST`; ambiguous ones use a preferred zone's offset with a warning, or
+   fail with the candidates), POSIX TZ strings (`EST5EDT,M3.2.0,M11.1.0`),
+   paths to TZif files, or aliases from the `[aliases]` table of the config
+   file (9), for example: `office = \"America/Chicago\"` or `team = [\"UTC\",
+   \"Asia/Kolkata\"]`.
 
 4. The `-f`, `-a`, and `-x` options are processed *in that order* and do not
    enable any reordering, however the `-n` option processes its arguments in the
-   order given and handles custom, \"a\", \"x\", and named formats.
+   order given and handles custom, \"a\", \"x\", and named formats. Custom
+   formats are checked first; an invalid specifier (`%K`) is reported with its
+   position and the exit code is 11.
 
 5. \"bcd\" format: year, month, day, hour, minute, and second displayed like a
-   binary clock with the Braille Patterns Unicode Block and `|` separators.
+   binary clock with the Braille Patterns Unicode Block and `|` separators;
+   \"bcd-clock\" and \"binary-clock\" formats: the same fields as a multi-line
+   binary clock with one column per digit or per field, respectively, using
+   the `--glyphs` glyph set.
 
 6. `-l` / `-z` are ignored when processing UTC-only formats like `-n rfc-3339`.
+
+7. \"dtg\" format: military date time group (`DDHHMMZ MON YY`) with the time
+   zone letter for the timezone's offset (`Z`: UTC, `A`-`I`: +1 to +9, `K`-`M`:
+   +10 to +12, `N`-`Y`: -1 to -12) or in UTC if there is no letter for it.
+
+8. `--plan`: prints each hour of the date (`YYYY-MM-DD` or a timestamp; default:
+   today) in the first timezone as the local time in each timezone, marking
+   those within the timezone's working hours (`--hours`) with `*` and hours
+   within everyone's working hours with `<= all`, followed by the best slots
+   (most timezones within working hours, then closest to the middle of them).
+
+9. Config file (`$XDG_CONFIG_HOME/dtg/config.toml` or
+   `~/.config/dtg/config.toml`): `formats` (named formats as with `-n`) and
+   `zones` if neither is given, `separator`, `[named-formats]` for `-n` (for
+   example: `stamp = \"%Y%m%d\"`; not a built-in name), `[aliases]`
+   (3), and `[profiles.NAME]` tables with any of these keys that override them
+   with `--profile NAME`.
+
+10. `--csv-column`: the first row is a header; the converted columns replace
+    the column (a single one keeps its header) or are appended (`--append`)
+    with `FORMAT (ZONE)` headers; rows with a missing column or an invalid
+    timestamp are reported with their line number and left unconverted, and
+    the exit code is 2.
 \
     ",
 )]
@@ -89,31 +194,143 @@ struct Cli {
     #[arg(short)]
     x_format: bool,
 
-    /// Give timestamp argument(s) in "x" format (2)
+    /// Give timestamp or interval argument(s) in "x" format (2)
     #[arg(short = 'X')]
     from_x: bool,
 
-    /// Search/list timezones
+    /// "x" format alphabet [standard, url-safe, dns, or any custom alphabet] (2)
+    #[arg(long, value_name = "ALPHABET")]
+    x_alphabet: Option<String>,
+
+    /// Give timestamp argument(s) in "bcd" format in the first timezone (5)
+    #[arg(short = 'B')]
+    from_bcd: bool,
+
+    /// Give timestamp argument(s) in military "dtg" format (7)
+    #[arg(short = 'M')]
+    from_military: bool,
+
+    /// Give timestamp argument(s) in a custom format in the first timezone unless it has an offset
+    /// [e.g. "%Y-%m-%d %H:%M"]
+    #[arg(long, value_name = "FORMAT")]
+    from_format: Option<String>,
+
+    /// Convert the timestamps in a column [header name or number] of CSV (or `--tsv`) from stdin
+    /// to each format and timezone (10)
+    #[arg(long, value_name = "COLUMN")]
+    csv_column: Option<String>,
+
+    /// Append the converted columns instead of replacing the column (--csv-column)
+    #[arg(long)]
+    append: bool,
+
+    /// Search (ignoring case, accents, spaces, `_`s, and `-`s; ranked) / list timezones
     #[arg(short = 'Z')]
     list_zones: bool,
 
+    /// List timezones with their offset, abbreviation, DST state, next transition, and local time
+    /// (-Z)
+    #[arg(long)]
+    long: bool,
+
+    /// List the UTC offset and abbreviation changes of a timezone after the first argument [default:
+    /// now] until the second [default: a year later]
+    #[arg(long, value_name = "ZONE")]
+    transitions: Option<String>,
+
+    /// Plan a meeting on the argument's date across the timezones (8)
+    #[arg(long)]
+    plan: bool,
+
+    /// Working hours for each timezone, the last for the rest [default: "9-17"; e.g.: "9-17,22-06"]
+    /// (8)
+    #[arg(long, value_name = "RANGES")]
+    hours: Option<String>,
+
+    /// Only list timezones in an ISO 3166 2-character country code [e.g. "BR"] (-Z)
+    #[arg(long, value_name = "CODE")]
+    country: Option<String>,
+
+    /// Sort timezones (-Z)
+    #[arg(long, value_name = "KEY", value_parser = ["name", "offset"], default_value = "name")]
+    sort: String,
+
+    /// Only list timezones with an offset [e.g. "+05:30"] (-Z)
+    #[arg(long, value_name = "OFFSET", allow_hyphen_values = true)]
+    offset: Option<String>,
+
     /// Format(s) [-z/-l: "%a %d %b %Y %H:%M:%S %Z", "%Y-%m-%dT%H:%M:%SZ"]
     #[arg(short, value_name = "FORMAT")]
     formats: Vec<String>,
 
     /// Timezone(s) [default: UTC] (3) (6)
-    #[arg(short)]
+    #[arg(short, allow_hyphen_values = true)]
     zone: Option<String>,
 
-    /// Separator [default: "\n"]
+    /// Separator [default: "\n"] (9)
     #[arg(short)]
     separator: Option<String>,
 
-    /// Named format(s) [all, bcd, compact-date (%Y%m%d), compact-date-time (%Y%m%d-%H%M%S),
-    /// compact-time (%H%M%S), default, rfc-3339, x, or any custom format] (4) (5)
+    /// Named format(s) [all, bcd, bcd-clock, binary-clock, compact-date (%Y%m%d),
+    /// compact-date-time (%Y%m%d-%H%M%S), compact-time (%H%M%S), default, dtg, rfc-3339,
+    /// x, x-extended, a config file named format, or any custom format] (2) (4) (5) (7) (9)
     #[arg(short, value_name = "NAME")]
     named_formats: Vec<String>,
 
+    /// Binary clock glyph set (5)
+    #[arg(
+        long,
+        value_name = "NAME",
+        value_parser = ["circles", "squares", "digits"],
+        default_value = "circles",
+    )]
+    glyphs: String,
+
+    /// Print a JSON array with an object for each timestamp: the input, epoch seconds and
+    /// nanoseconds, and for each timezone, RFC 3339, offset, abbreviation, DST flag, and an array
+    /// of each format and its value
+    #[arg(long)]
+    json: bool,
+
+    /// Print the `--json` objects as newline-delimited JSON (one per line)
+    #[arg(long)]
+    ndjson: bool,
+
+    /// Print CSV with a header and a row for each timestamp with a column for each format and
+    /// timezone
+    #[arg(long)]
+    csv: bool,
+
+    /// Print `--csv` as TSV
+    #[arg(long)]
+    tsv: bool,
+
+    /// Print a table for each timestamp with a row for each timezone and a column for each format
+    #[arg(long)]
+    table: bool,
+
+    /// Border style of `--table`
+    #[arg(
+        long,
+        value_name = "STYLE",
+        value_parser = ["unicode", "ascii"],
+        default_value = "unicode",
+    )]
+    table_style: String,
+
+    /// Print `--table` with a row for each format and a column for each timezone
+    #[arg(long)]
+    transpose: bool,
+
+    /// Highlight the local timezone in `--table`
+    #[arg(
+        long,
+        value_name = "WHEN",
+        value_parser = ["auto", "always", "never"],
+        default_value = "auto",
+    )]
+    color: String,
+
     /// Run every N seconds
     #[arg(short, value_name = "N")]
     interval: Option<f32>,
@@ -122,11 +339,33 @@ struct Cli {
     #[arg(short, value_name = "N")]
     clear: Option<f32>,
 
+    /// Timezone database [system, bundled, zoneinfo directory, or concatenated tzdata file; default:
+    /// `DTG_TZDB` or system]
+    #[arg(long, value_name = "SOURCE")]
+    tzdb: Option<String>,
+
+    /// List the named formats with a sample of the argument [default: now] in the first timezone
+    #[arg(long)]
+    list_formats: bool,
+
+    /// Use a config file profile (9)
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Print the effective config file settings (9)
+    #[arg(long)]
+    config_dump: bool,
+
     /// Print the readme
     #[arg(short, long)]
     readme: bool,
 
-    /// Argument [-X: "x" timestamp (2), -Z: timezone, "%s.%f" timestamp, default: now]
+    /// Print version
+    #[arg(short = 'V', long)]
+    version: bool,
+
+    /// Argument [-X: "x" timestamp (2), -B: "bcd" timestamp (5), -M: "dtg" timestamp (7), -Z:
+    /// timezone, "%s.%f" timestamp, default: now]
     #[arg(name = "ARG")]
     args: Vec<String>,
 }
@@ -135,6 +374,28 @@ struct Cli {
 fn main() {
     let cli = Cli::parse();
 
+    let config = Config::load(cli.profile.as_deref());
+
+    if cli.config_dump {
+        config.dump(cli.local_zone, cli.a_format);
+        return;
+    }
+
+    if let Some(source) = cli.tzdb.clone().or_else(|| std::env::var("DTG_TZDB").ok())
+        && let Err(e) = tz_db_set(&source)
+    {
+        error_(&e);
+    }
+
+    if cli.version {
+        println!(
+            "dtg {} (tzdb {})",
+            env!("CARGO_PKG_VERSION"),
+            tz_db_version().as_deref().unwrap_or("unknown"),
+        );
+        return;
+    }
+
     if cli.readme {
         #[cfg(unix)]
         Pager::with_pager("bat -pl md").setup();
@@ -144,28 +405,7 @@ fn main() {
     }
 
     if cli.list_zones {
-        let mut found = 0;
-        let zones = jiff::tz::db().available().filter(|x| {
-            !x.to_string().starts_with("right/") && !["Factory", "posixrules"].contains(&x.as_str())
-        });
-        if cli.args.is_empty() {
-            for zone in zones {
-                println!("{zone}");
-            }
-        } else {
-            let search = &cli.args[0];
-            let search_lc = search.to_lowercase();
-            for zone in zones {
-                let name = zone.to_string().to_lowercase();
-                if name.contains(&search_lc) {
-                    println!("{zone}");
-                    found += 1;
-                }
-            }
-            if found == 0 {
-                error(1, &format!("Zero timezones found matching `{search}`"));
-            }
-        }
+        list_zones(&cli);
         return;
     }
 
@@ -174,12 +414,56 @@ fn main() {
         error(6, "Options `-i` and `-c` are mutually exclusive");
         return;
     }
+    exclusive(&[
+        ("-X", cli.from_x),
+        ("-B", cli.from_bcd),
+        ("-M", cli.from_military),
+        ("--from-format", cli.from_format.is_some()),
+    ]);
+    exclusive(&[
+        ("--json", cli.json),
+        ("--ndjson", cli.ndjson),
+        ("--csv", cli.csv),
+        ("--tsv", cli.tsv),
+        ("--table", cli.table),
+    ]);
+    let output = if cli.json {
+        Output::Json
+    } else if cli.ndjson {
+        Output::Ndjson
+    } else if cli.csv {
+        Output::Delimited(',')
+    } else if cli.tsv {
+        Output::Delimited('\t')
+    } else if cli.table {
+        Output::Table(Table {
+            ascii: cli.table_style == "ascii",
+            transpose: cli.transpose,
+            color: match cli.color.as_str() {
+                "always" => true,
+                "never" => false,
+                _ => std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+            },
+        })
+    } else {
+        Output::Text
+    };
+    if cli.json && (cli.interval.is_some() || clear) {
+        error(
+            6,
+            &format!(
+                "Options `--json` and `{}` are mutually exclusive (use `--ndjson`)",
+                if clear { "-c" } else { "-i" },
+            ),
+        );
+        return;
+    }
     let interval = match cli.interval {
         Some(f) => Some(std::time::Duration::from_secs_f32(f)),
         None => cli.clear.map(std::time::Duration::from_secs_f32),
     };
 
-    let separator = match cli.separator {
+    let separator = match cli.separator.or_else(|| config.separator.clone()) {
         Some(s) => match s.as_str() {
             "\\n" => String::from("\n"),
             "\\t" => String::from("\t"),
@@ -188,109 +472,1098 @@ fn main() {
         None => String::from("\n"),
     };
 
+    let x_alphabet = x_alphabet_(cli.x_alphabet.as_deref());
+
     let mut formats = vec![];
     for i in &cli.formats {
-        formats.push(Format::Custom(i.clone()));
+        formats.push((i.clone(), Format::Custom(i.clone())));
     }
     if cli.a_format {
-        formats.push(Format::A);
+        formats.push((String::from("a"), Format::A));
     }
     if cli.x_format {
-        formats.push(Format::X);
-    }
-    for n in &cli.named_formats {
-        formats.push(match n.as_str() {
-            "a" | "all" => Format::A,
-            "cd" | "compact-date" => Format::Custom(String::from("%Y%m%d")),
-            "cdt" | "compact-date-time" => Format::Custom(String::from("%Y%m%d-%H%M%S")),
-            "ct" | "compact-time" => Format::Custom(String::from("%H%M%S")),
-            "d" | "default" => Format::default(),
-            "i" | "r" | "iso" | "rfc" | "rfc-3339" => Format::rfc_3339(),
-            "x" => Format::X,
-            "bcd" => Format::BCD,
-            _ => Format::Custom(n.clone()),
-        });
+        formats.push((String::from("x"), Format::XWith(x_alphabet.clone())));
     }
+    let glyphs = match cli.glyphs.as_str() {
+        "squares" => ClockGlyphs::Squares,
+        "digits" => ClockGlyphs::Digits,
+        _ => ClockGlyphs::Circles,
+    };
+    let mut registry = FormatRegistry::new_with(&x_alphabet, glyphs);
+    for (name, format) in &config.named_formats {
+        registry
+            .register(name, Format::custom(format), format)
+            .unwrap();
+    }
+    let named_format = |n: &String| {
+        let format = registry.get(n).cloned().map_or_else(|| n.parse(), Ok);
+        if let Err(ref e) = format {
+            error_(e);
+        }
+        (n.clone(), format.unwrap())
+    };
+    formats.extend(cli.named_formats.iter().map(named_format));
+    if formats.is_empty()
+        && let Some(defaults) = &config.formats
+    {
+        formats.extend(defaults.iter().map(named_format));
+    }
+    for (_, format) in &formats {
+        if let Err(e) = format.validate() {
+            error_(&e);
+        }
+    }
+    let zone = cli.zone.clone().or_else(|| {
+        config
+            .zones
+            .as_ref()
+            .filter(|_| !cli.local_zone)
+            .map(|x| x.join(","))
+    });
     if formats.is_empty() {
-        formats.push(if cli.local_zone || cli.zone.is_some() {
-            Format::default()
+        formats.push(if cli.local_zone || zone.is_some() {
+            (String::from("default"), Format::default())
         } else {
-            Format::rfc_3339()
+            (String::from("rfc-3339"), Format::rfc_3339())
         });
     }
-    let mut zones = vec![];
-    match cli.zone {
-        Some(s) => {
-            for i in s.split(',') {
-                zones.push(tz_(i));
-            }
-        }
+    let names = match &zone {
+        Some(s) => tz_split(s),
         None => {
             if cli.local_zone || cli.a_format {
-                zones.push(tz_("local"));
+                vec![String::from("local")]
             } else {
-                zones.push(tz_("UTC"));
+                vec![String::from("UTC")]
             }
         }
+    };
+    let columns = Columns {
+        formats: formats
+            .into_iter()
+            .map(|(label, format)| (label, Some(format)))
+            .collect(),
+        zones: names
+            .into_iter()
+            .map(|x| {
+                let tz = tz_(&x);
+                (x, tz)
+            })
+            .collect(),
+    };
+    let input = if cli.from_x {
+        Input::X(x_alphabet)
+    } else if cli.from_bcd {
+        Input::Bcd(columns.zones[0].1.clone().unwrap_or(TimeZone::UTC))
+    } else if cli.from_military {
+        Input::Military
+    } else if let Some(format) = &cli.from_format {
+        Input::Format(
+            format.clone(),
+            columns.zones[0].1.clone().unwrap_or(TimeZone::UTC),
+        )
+    } else {
+        Input::Epoch
+    };
+    if cli.list_formats {
+        list_formats(&registry, &cli.args, columns.zones[0].1.as_ref(), &input);
+        return;
+    }
+    if let Some(zone) = &cli.transitions {
+        transitions(zone, &cli.args, &columns, &separator, &input);
+        return;
+    }
+    if cli.plan {
+        plan(&cli.args, &columns.zones, cli.hours.as_deref(), &input);
+        return;
+    }
+    if let Some(column) = &cli.csv_column {
+        let delimiter = if cli.tsv { '\t' } else { ',' };
+        csv_column(column, &columns, &input, delimiter, cli.append);
+        return;
     }
-    let formats = formats
-        .iter()
-        .map(|x| Some(x.clone()))
-        .collect::<Vec<Option<Format>>>();
     if let Some(duration) = interval {
+        let mut header = true;
         loop {
             if clear {
                 clearscreen::clear().unwrap();
             }
-            core(&cli.args, &formats, &zones, &separator, cli.from_x);
+            core(&cli.args, &columns, &separator, &input, output, header);
+            header = clear;
             std::thread::sleep(duration);
         }
     } else {
-        core(&cli.args, &formats, &zones, &separator, cli.from_x);
+        core(&cli.args, &columns, &separator, &input, output, true);
     }
 }
 
+/// Exit if more than one of the mutually exclusive options is given
+fn exclusive(options: &[(&str, bool)]) {
+    let given = options
+        .iter()
+        .filter_map(|(option, given)| given.then_some(*option))
+        .collect::<Vec<&str>>();
+    if given.len() > 1 {
+        error(
+            6,
+            &format!("Options `{}` are mutually exclusive", given.join("` and `")),
+        );
+    }
+}
+
+/// Timestamp argument format
+enum Input {
+    Epoch,
+    X(XAlphabet),
+    Bcd(TimeZone),
+    Military,
+    Format(String, TimeZone),
+}
+
+/// Output mode
+#[derive(Clone, Copy)]
+enum Output {
+    Text,
+    Json,
+    Ndjson,
+    Delimited(char),
+    Table(Table),
+}
+
+/// Table output options
+#[derive(Clone, Copy)]
+struct Table {
+    ascii: bool,
+    transpose: bool,
+    color: bool,
+}
+
+/// Formats and timezones with their labels
+struct Columns {
+    formats: Vec<(String, Option<Format>)>,
+    zones: Vec<(String, Option<TimeZone>)>,
+}
+
 fn core(
     args: &[String],
-    formats: &[Option<Format>],
-    timezones: &[Option<TimeZone>],
+    columns: &Columns,
     separator: &str,
-    from_x: bool,
+    input: &Input,
+    output: Output,
+    header: bool,
 ) {
-    let mut dtgs = vec![];
-    for arg in args {
-        let dtg = if from_x {
-            Dtg::from_x(arg)
+    let mut dtgs = args
+        .iter()
+        .map(|x| (Some(x.as_str()), dtg_(x, input)))
+        .collect::<Vec<_>>();
+    if dtgs.is_empty() {
+        dtgs.push((None, (Dtg::now(), None)));
+    }
+    if let Output::Delimited(delimiter) = output
+        && header
+    {
+        let header = columns
+            .formats
+            .iter()
+            .flat_map(|(label, _)| {
+                columns
+                    .zones
+                    .iter()
+                    .map(move |(name, _)| delimited_(&format!("{label} ({name})"), delimiter))
+            })
+            .collect::<Vec<String>>();
+        println!("{}", header.join(&delimiter.to_string()));
+    }
+    let mut objects = vec![];
+    let mut tables = 0;
+    for (arg, (i, end)) in dtgs {
+        match output {
+            Output::Text | Output::Delimited(_) | Output::Table(_) => {}
+            Output::Json | Output::Ndjson => {
+                let mut object = format!(
+                    "{{\"input\":{},{}",
+                    arg.map_or_else(|| String::from("null"), quoted),
+                    json_(&i, columns),
+                );
+                if let Some(end) = &end {
+                    write!(object, ",\"end\":{{{}}}", json_(end, columns)).unwrap();
+                }
+                object.push('}');
+                objects.push(object);
+                continue;
+            }
+        }
+        let t = values_(&i, end.as_ref(), columns);
+        match output {
+            Output::Delimited(delimiter) => {
+                let t = t
+                    .iter()
+                    .map(|x| delimited_(x, delimiter))
+                    .collect::<Vec<String>>();
+                println!("{}", t.join(&delimiter.to_string()));
+            }
+            Output::Table(table) => {
+                if tables > 0 {
+                    println!();
+                }
+                print!("{}", table_(&t, columns, table));
+                tables += 1;
+            }
+            _ => println!("{}", t.join(separator)),
+        }
+    }
+    match output {
+        Output::Text | Output::Delimited(_) | Output::Table(_) => {}
+        Output::Json => println!("[{}]", objects.join(",")),
+        Output::Ndjson => {
+            for object in objects {
+                println!("{object}");
+            }
+        }
+    }
+}
+
+/// Format a timestamp or interval in each format and timezone
+fn values_(dtg: &Dtg, end: Option<&Dtg>, columns: &Columns) -> Vec<String> {
+    let mut r = vec![];
+    for (_, fmt) in &columns.formats {
+        for (_, tz) in &columns.zones {
+            r.push(match end {
+                Some(end) => format!("{}/{}", dtg.format(fmt, tz), end.format(fmt, tz)),
+                None => dtg.format(fmt, tz),
+            });
+        }
+    }
+    r
+}
+
+/// Render the values for each format and timezone as a table with a row for each timezone and a
+/// column for each format (or transposed), highlighting the local timezone if coloring
+fn table_(values: &[String], columns: &Columns, table: Table) -> String {
+    let local = local_zones(columns);
+    let n = columns.zones.len();
+    let mut rows = vec![];
+    if table.transpose {
+        rows.push(
+            std::iter::once("FORMAT")
+                .chain(columns.zones.iter().map(|x| x.0.as_str()))
+                .collect::<Vec<&str>>(),
+        );
+        for (i, (label, _)) in columns.formats.iter().enumerate() {
+            rows.push(
+                std::iter::once(label.as_str())
+                    .chain(values[i * n..(i + 1) * n].iter().map(String::as_str))
+                    .collect(),
+            );
+        }
+    } else {
+        rows.push(
+            std::iter::once("ZONE")
+                .chain(columns.formats.iter().map(|x| x.0.as_str()))
+                .collect::<Vec<&str>>(),
+        );
+        for (i, (name, _)) in columns.zones.iter().enumerate() {
+            rows.push(
+                std::iter::once(name.as_str())
+                    .chain(values.iter().skip(i).step_by(n).map(String::as_str))
+                    .collect(),
+            );
+        }
+    }
+    let highlight = |row: usize, column: usize| {
+        table.color
+            && if table.transpose {
+                column > 0 && local[column - 1]
+            } else {
+                row > 0 && local[row - 1]
+            }
+    };
+    let mut widths = vec![0; rows[0].len()];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = cell
+                .lines()
+                .map(UnicodeWidthStr::width)
+                .max()
+                .unwrap_or(0)
+                .max(*width);
+        }
+    }
+    let multi_line = rows.iter().flatten().any(|x| x.contains('\n'));
+    let [top, middle, bottom, vertical] = if table.ascii {
+        [
+            ["+", "-", "+", "+"],
+            ["+", "-", "+", "+"],
+            ["+", "-", "+", "+"],
+            ["|"; 4],
+        ]
+    } else {
+        [
+            ["┌", "─", "┬", "┐"],
+            ["├", "─", "┼", "┤"],
+            ["└", "─", "┴", "┘"],
+            ["│"; 4],
+        ]
+    };
+    let border = |[left, horizontal, cross, right]: [&str; 4]| {
+        let line = widths
+            .iter()
+            .map(|width| horizontal.repeat(width + 2))
+            .collect::<Vec<String>>()
+            .join(cross);
+        format!("{left}{line}{right}\n")
+    };
+    let mut r = border(top);
+    for (i, row) in rows.iter().enumerate() {
+        if i == 1 || (i > 1 && multi_line) {
+            r.push_str(&border(middle));
+        }
+        let lines = row
+            .iter()
+            .map(|x| x.lines().collect::<Vec<&str>>())
+            .collect::<Vec<_>>();
+        for line in 0..lines.iter().map(Vec::len).max().unwrap_or(0).max(1) {
+            r.push_str(vertical[0]);
+            for (j, (cell, width)) in lines.iter().zip(&widths).enumerate() {
+                let text = cell.get(line).copied().unwrap_or_default();
+                let padding = " ".repeat(width - text.width());
+                if highlight(i, j) {
+                    write!(r, " \x1b[1;32m{text}\x1b[0m{padding} ").unwrap();
+                } else {
+                    write!(r, " {text}{padding} ").unwrap();
+                }
+                r.push_str(vertical[0]);
+            }
+            r.push('\n');
+        }
+    }
+    r.push_str(&border(bottom));
+    r
+}
+
+/// Whether each timezone is the local timezone
+fn local_zones(columns: &Columns) -> Vec<bool> {
+    let local = tz("local").ok();
+    columns
+        .zones
+        .iter()
+        .map(|(name, tz)| {
+            name == "local"
+                || tz
+                    .as_ref()
+                    .and_then(TimeZone::iana_name)
+                    .is_some_and(|x| local.as_ref().and_then(TimeZone::iana_name) == Some(x))
+        })
+        .collect()
+}
+
+/// Quote a CSV/TSV field if it contains the delimiter, a quote, or a line break
+fn delimited_(s: &str, delimiter: char) -> String {
+    if s.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Get the JSON object members for a [Dtg]: epoch seconds and nanoseconds, and for each timezone,
+/// RFC 3339, offset, abbreviation, DST flag, and an array of each format and its rendering
+fn json_(dtg: &Dtg, columns: &Columns) -> String {
+    let dt = dtg.timestamp();
+    let zones = columns
+        .zones
+        .iter()
+        .map(|(name, tz)| {
+            let zone = tz.clone().unwrap_or(TimeZone::UTC);
+            let info = zone.to_offset_info(dt);
+            let formats = columns
+                .formats
+                .iter()
+                .map(|(label, fmt)| {
+                    format!(
+                        "{{\"format\":{},\"value\":{}}}",
+                        quoted(label),
+                        quoted(&dtg.format(fmt, tz)),
+                    )
+                })
+                .collect::<Vec<String>>();
+            format!(
+                concat!(
+                    "{{\"zone\":{},\"rfc_3339\":{},\"offset\":{},",
+                    "\"abbreviation\":{},\"dst\":{},\"formats\":[{}]}}",
+                ),
+                quoted(name),
+                quoted(&dt.display_with_offset(info.offset()).to_string()),
+                quoted(&offset_(info.offset())),
+                quoted(info.abbreviation()),
+                info.dst().is_dst(),
+                formats.join(","),
+            )
+        })
+        .collect::<Vec<String>>();
+    format!(
+        "\"seconds\":{},\"nanoseconds\":{},\"zones\":[{}]",
+        dt.as_second(),
+        dt.subsec_nanosecond(),
+        zones.join(","),
+    )
+}
+
+/// Config file settings (9)
+#[derive(Default)]
+struct Config {
+    path: Option<PathBuf>,
+    profile: Option<String>,
+    formats: Option<Vec<String>>,
+    zones: Option<Vec<String>>,
+    separator: Option<String>,
+    named_formats: BTreeMap<String, String>,
+    aliases: BTreeMap<String, Vec<String>>,
+}
+
+impl Config {
+    /// Read the config file, if any, apply a profile, and define the timezone aliases
+    fn load(profile: Option<&str>) -> Config {
+        let mut config = Config {
+            profile: profile.map(String::from),
+            ..Config::default()
+        };
+        let Some(path) = config_path().filter(|x| x.is_file()) else {
+            if let Some(profile) = profile {
+                error(9, &format!("Profile `{profile}` not found"));
+            }
+            return config;
+        };
+        let invalid = |msg: &str| {
+            error(
+                9,
+                &format!("Invalid config file `{}`: {msg}", path.display()),
+            );
+        };
+        let doc = match std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|x| {
+                x.parse::<DocumentMut>()
+                    .map_err(|e| e.message().to_string())
+            }) {
+            Ok(doc) => doc,
+            Err(e) => {
+                invalid(&e);
+                return config;
+            }
+        };
+        if let Err(e) = config.apply(doc.as_table(), "") {
+            invalid(&e);
+        }
+        if let Some(profile) = profile {
+            match doc
+                .get("profiles")
+                .and_then(|x| x.get(profile))
+                .and_then(Item::as_table_like)
+            {
+                Some(table) => {
+                    if let Err(e) = config.apply(table, &format!("profiles.{profile}.")) {
+                        invalid(&e);
+                    }
+                }
+                None => invalid(&format!("Profile `{profile}` not found")),
+            }
+        }
+        for (alias, zones) in &config.aliases {
+            if let Err(e) = tz_alias_set(alias, &zones.join(",")) {
+                invalid(&message(&e));
+            }
+        }
+        config.path = Some(path);
+        config
+    }
+
+    /// Apply the settings in a table of the config file
+    fn apply(&mut self, table: &dyn TableLike, prefix: &str) -> Result<(), String> {
+        for (key, item) in table.iter() {
+            let e = |what: &str| format!("`{prefix}{key}` is not {what}");
+            let strings_e = || e("a string or an array of strings");
+            match key {
+                "formats" => self.formats = Some(strings(item).ok_or_else(strings_e)?),
+                "zones" => self.zones = Some(strings(item).ok_or_else(strings_e)?),
+                "separator" => {
+                    self.separator = Some(item.as_str().ok_or_else(|| e("a string"))?.to_string());
+                }
+                "named-formats" => {
+                    for (name, format) in item.as_table_like().ok_or_else(|| e("a table"))?.iter() {
+                        let format = format
+                            .as_str()
+                            .ok_or_else(|| format!("`{prefix}{key}.{name}` is not a string"))?;
+                        Format::compile(format)
+                            .and_then(|x| FormatRegistry::new().register(name, x, format))
+                            .map_err(|e| message(&e))?;
+                        self.named_formats
+                            .insert(name.to_string(), format.to_string());
+                    }
+                }
+                "aliases" => {
+                    for (alias, zones) in item.as_table_like().ok_or_else(|| e("a table"))?.iter() {
+                        let zones = strings(zones).ok_or_else(|| {
+                            format!(
+                                "`{prefix}{key}.{alias}` is not a string or an array of strings"
+                            )
+                        })?;
+                        self.aliases.insert(alias.to_string(), zones);
+                    }
+                }
+                "profiles" if prefix.is_empty() => {
+                    for (name, profile) in item.as_table_like().ok_or_else(|| e("a table"))?.iter()
+                    {
+                        let profile = profile
+                            .as_table_like()
+                            .ok_or_else(|| format!("`{key}.{name}` is not a table"))?;
+                        Config::default().apply(profile, &format!("{key}.{name}."))?;
+                    }
+                }
+                _ => return Err(format!("Unknown key `{prefix}{key}`")),
+            }
+        }
+        Ok(())
+    }
+
+    /// Print the effective settings (defaults for those not set, as
+    /// adjusted by `-l` and `-a`) as TOML
+    fn dump(&self, local_zone: bool, a_format: bool) {
+        match (&self.path, &self.profile) {
+            (Some(path), Some(profile)) => {
+                println!("# {} (profile: {profile})", path.display());
+            }
+            (Some(path), None) => println!("# {}", path.display()),
+            (None, _) => println!("# No config file"),
+        }
+        let array = |x: &[String]| {
+            format!(
+                "[{}]",
+                x.iter()
+                    .map(|x| quoted(x))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        };
+        let default = |set: bool| if set { "" } else { " # default" };
+        let zones = self.zones.as_ref().filter(|_| !local_zone);
+        let (formats, note) = if a_format {
+            (vec![String::from("a")], " # -a")
+        } else if let Some(formats) = &self.formats {
+            (formats.clone(), "")
+        } else if local_zone || zones.is_some() {
+            (vec![String::from("default")], " # default")
         } else {
-            Dtg::from(arg)
+            (vec![String::from("rfc-3339")], " # default")
         };
-        if dtg.is_err() {
-            error(2, &format!("Invalid timestamp: `{arg}`"));
+        println!("formats = {}{note}", array(&formats));
+        let (zones, note) = match zones {
+            Some(zones) => (zones.clone(), ""),
+            None if local_zone => (vec![String::from("local")], " # -l"),
+            None if a_format => (vec![String::from("local")], " # default"),
+            None => (vec![String::from("UTC")], " # default"),
+        };
+        println!("zones = {}{note}", array(&zones));
+        println!(
+            "separator = {}{}",
+            quoted(self.separator.as_deref().unwrap_or("\\n")),
+            default(self.separator.is_some()),
+        );
+        if !self.named_formats.is_empty() {
+            println!("\n[named-formats]");
+            for (name, format) in &self.named_formats {
+                println!("{} = {}", toml_key(name), quoted(format));
+            }
+        }
+        if !self.aliases.is_empty() {
+            println!("\n[aliases]");
+            for (alias, zones) in &self.aliases {
+                println!("{} = {}", toml_key(alias), array(zones));
+            }
         }
-        dtgs.push(dtg.unwrap());
     }
-    if dtgs.is_empty() {
-        dtgs.push(Dtg::now());
+}
+
+/// Get the config file path (`$XDG_CONFIG_HOME/dtg/config.toml` or
+/// `$HOME/.config/dtg/config.toml`)
+fn config_path() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|x| !x.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|x| PathBuf::from(x).join(".config")))
+        .map(|x| x.join("dtg").join("config.toml"))
+}
+
+/// Get a config file string or array of strings
+fn strings(item: &Item) -> Option<Vec<String>> {
+    match item.as_value()? {
+        Value::String(x) => Some(vec![x.value().clone()]),
+        Value::Array(x) => x.iter().map(|x| x.as_str().map(String::from)).collect(),
+        _ => None,
+    }
+}
+
+/// Format a TOML key (bare if possible)
+fn toml_key(s: &str) -> String {
+    if !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        s.to_string()
+    } else {
+        quoted(s)
     }
-    for i in dtgs {
+}
+
+/// Format a TOML basic or JSON string
+fn quoted(s: &str) -> String {
+    let mut r = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => r.push_str("\\\""),
+            '\\' => r.push_str("\\\\"),
+            '\n' => r.push_str("\\n"),
+            '\t' => r.push_str("\\t"),
+            '\r' => r.push_str("\\r"),
+            c if c.is_control() => write!(r, "\\u{:04X}", u32::from(c)).unwrap(),
+            c => r.push(c),
+        }
+    }
+    r.push('"');
+    r
+}
+
+/// Parse a timestamp or interval argument, or exit
+fn dtg_(arg: &str, input: &Input) -> (Dtg, Option<Dtg>) {
+    let dtg = parse_(arg, input);
+    if let Err(e) = &dtg {
+        error_(e);
+    }
+    dtg.unwrap()
+}
+
+/// Parse a timestamp or interval
+fn parse_(arg: &str, input: &Input) -> Result<(Dtg, Option<Dtg>), DtgError> {
+    match input {
+        Input::Epoch => Dtg::from(arg).map(|x| (x, None)),
+        Input::X(alphabet) if arg.contains('/') => {
+            Dtg::from_x_interval_with(arg, alphabet).map(|(x, y)| (x, Some(y)))
+        }
+        Input::X(alphabet) => Dtg::from_x_with(arg, alphabet).map(|x| (x, None)),
+        Input::Bcd(tz) => Dtg::from_bcd(arg, tz).map(|x| (x, None)),
+        Input::Military => Dtg::from_military(arg).map(|x| (x, None)),
+        Input::Format(format, tz) => Dtg::from_format(arg, format, tz).map(|x| (x, None)),
+    }
+}
+
+/// Convert the timestamps in a column of CSV/TSV from stdin to each format and timezone
+fn csv_column(column: &str, columns: &Columns, input: &Input, delimiter: char, append: bool) {
+    let mut lines = std::io::stdin().lock().split(b'\n');
+    let mut line = 1;
+    let mut next = |line: usize| {
+        record_(&mut lines, delimiter).unwrap_or_else(|e| {
+            error(1, &format!("Line {line}: Failed to read: {e}"));
+            None
+        })
+    };
+    let join = |fields: &[String]| {
+        fields
+            .iter()
+            .map(|x| delimited_(x, delimiter))
+            .collect::<Vec<String>>()
+            .join(&delimiter.to_string())
+    };
+    let Some((header, n)) = next(line) else {
+        return;
+    };
+    let Ok(header) = header else {
+        error(1, &format!("Line {line}: Invalid UTF-8"));
+        return;
+    };
+    line += n;
+    let index = header.iter().position(|x| x == column).or_else(|| {
+        column
+            .parse::<usize>()
+            .ok()
+            .filter(|x| (1..=header.len()).contains(x))
+            .map(|x| x - 1)
+    });
+    let Some(index) = index else {
+        error(1, &format!("Column `{column}` not found"));
+        return;
+    };
+    let mut converted = columns
+        .formats
+        .iter()
+        .flat_map(|(label, _)| {
+            columns
+                .zones
+                .iter()
+                .map(move |(name, _)| format!("{label} ({name})"))
+        })
+        .collect::<Vec<String>>();
+    if !append && converted.len() == 1 {
+        converted[0].clone_from(&header[index]);
+    }
+    println!("{}", join(&splice_(header, index, converted, append)));
+    let mut malformed = 0;
+    while let Some((fields, n)) = next(line) {
+        let start = line;
+        line += n;
+        let fields = match fields {
+            Ok(fields) => fields,
+            Err(mut raw) => {
+                eprintln!("WARNING: Line {start}: Invalid UTF-8!");
+                malformed += 1;
+                raw.push(b'\n');
+                std::io::Write::write_all(&mut std::io::stdout(), &raw).ok();
+                continue;
+            }
+        };
+        if fields.len() == 1 && fields[0].is_empty() {
+            println!();
+            continue;
+        }
+        let Some(value) = fields.get(index) else {
+            eprintln!("WARNING: Line {start}: Missing column `{column}`!");
+            malformed += 1;
+            println!("{}", join(&fields));
+            continue;
+        };
+        let converted = match parse_(value, input) {
+            Ok((dtg, end)) => values_(&dtg, end.as_ref(), columns),
+            Err(e) => {
+                eprintln!("WARNING: Line {start}: {}!", message(&e));
+                malformed += 1;
+                let mut converted =
+                    vec![String::new(); columns.formats.len() * columns.zones.len()];
+                if !append {
+                    converted[0].clone_from(value);
+                }
+                converted
+            }
+        };
+        println!("{}", join(&splice_(fields, index, converted, append)));
+    }
+    if malformed > 0 {
+        error(2, &format!("Failed to convert {malformed} row(s)"));
+    }
+}
+
+/// Replace a field with the converted fields or append them
+fn splice_(
+    mut fields: Vec<String>,
+    index: usize,
+    converted: Vec<String>,
+    append: bool,
+) -> Vec<String> {
+    if append {
+        fields.extend(converted);
+    } else {
+        fields.splice(index..=index, converted);
+    }
+    fields
+}
+
+/// CSV/TSV record fields (or the raw bytes if not valid UTF-8) and the number of lines it spans
+type Record = (Result<Vec<String>, Vec<u8>>, usize);
+
+/// Read a CSV/TSV record (quoted fields may contain the delimiter, `""`, and line breaks) and the
+/// number of lines it spans; a record that is not valid UTF-8 is returned as its raw bytes
+fn record_(
+    lines: &mut impl Iterator<Item = std::io::Result<Vec<u8>>>,
+    delimiter: char,
+) -> std::io::Result<Option<Record>> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut raw = vec![];
+    let mut valid = true;
+    let mut n = 0;
+    loop {
+        let bytes = match lines.next() {
+            Some(bytes) => bytes?,
+            None if n == 0 => return Ok(None),
+            None => break,
+        };
+        if n > 0 {
+            raw.push(b'\n');
+        }
+        raw.extend_from_slice(&bytes);
+        n += 1;
+        valid &= std::str::from_utf8(&bytes).is_ok();
+        let line = String::from_utf8_lossy(&bytes);
+        let mut chars = line.trim_end_matches('\r').chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted => {
+                    if chars.peek() == Some(&'"') {
+                        chars.next();
+                        field.push('"');
+                    } else {
+                        quoted = false;
+                    }
+                }
+                '"' if field.is_empty() => quoted = true,
+                c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+                c => field.push(c),
+            }
+        }
+        if !quoted {
+            break;
+        }
+        field.push('\n');
+    }
+    fields.push(field);
+    Ok(Some((if valid { Ok(fields) } else { Err(raw) }, n)))
+}
+
+fn transitions(zone: &str, args: &[String], columns: &Columns, separator: &str, input: &Input) {
+    let Some(tz) = tz_(zone) else {
+        return;
+    };
+    let (from, end) = match args.first() {
+        Some(arg) => dtg_(arg, input),
+        None => (Dtg::now(), None),
+    };
+    let to = match (args.get(1), end) {
+        (Some(arg), _) => dtg_(arg, input).0,
+        (None, Some(end)) => end,
+        (None, None) => Dtg::from_dt(
+            &from
+                .timestamp()
+                .checked_add(SignedDuration::from_hours(366 * 24))
+                .unwrap_or(Timestamp::MAX),
+        ),
+    };
+    for transition in ZoneTransition::list(&tz, &from, &to) {
         let mut t = vec![];
-        for fmt in formats {
-            for tz in timezones {
-                t.push(i.format(fmt, tz));
+        for (_, fmt) in &columns.formats {
+            for (_, tz) in &columns.zones {
+                t.push(transition.dtg.format(fmt, tz));
             }
         }
-        println!("{}", t.join(separator));
+        println!(
+            "{} {} {} -> {} {}{}",
+            t.join(separator),
+            offset_(transition.offset_before),
+            transition.abbreviation_before,
+            offset_(transition.offset_after),
+            transition.abbreviation_after,
+            if transition.dst { " (DST)" } else { "" },
+        );
+    }
+}
+
+fn plan(
+    args: &[String],
+    timezones: &[(String, Option<TimeZone>)],
+    hours: Option<&str>,
+    input: &Input,
+) {
+    let names = timezones.iter().map(|x| x.0.clone()).collect::<Vec<_>>();
+    let mut zones = vec![];
+    let mut ranges = hours.unwrap_or("9-17").split(',').peekable();
+    let mut range = WorkingHours::default();
+    for (_, tz) in timezones {
+        if let Some(i) = ranges.next() {
+            let t = WorkingHours::new(i);
+            if let Err(ref e) = t {
+                error_(e);
+            }
+            range = t.unwrap();
+        }
+        zones.push((tz.clone().unwrap_or(TimeZone::UTC), range));
+    }
+    if ranges.peek().is_some() {
+        error(10, "More working hours than timezones");
+    }
+    let first = &zones[0].0;
+    let date = match args.first() {
+        Some(arg) => arg
+            .parse::<Date>()
+            .unwrap_or_else(|_| first.to_datetime(dtg_(arg, input).0.timestamp()).date()),
+        None => first.to_datetime(Timestamp::now()).date(),
+    };
+    let slots = MeetingSlot::list(date, &zones);
+    if let Err(ref e) = slots {
+        error_(e);
+    }
+    let slots = slots.unwrap();
+    let widths = names
+        .iter()
+        .map(|x| UnicodeWidthStr::width(x.as_str()).max(10))
+        .collect::<Vec<_>>();
+    let cells = |slot: &MeetingSlot| {
+        slot.local
+            .iter()
+            .zip(&slot.working)
+            .map(|(local, working)| {
+                format!(
+                    "{}{}",
+                    local.strftime("%a %H:%M"),
+                    if *working { "*" } else { "" }
+                )
+            })
+            .collect::<Vec<String>>()
+    };
+    let row = |cells: &[String]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell}{}", " ".repeat(width - cell.width())))
+            .collect::<Vec<String>>()
+            .join("  ")
+    };
+    println!("{}", row(&names).trim_end());
+    for slot in &slots {
+        let all = if slot.working_count() == zones.len() {
+            "  <= all"
+        } else {
+            ""
+        };
+        println!("{}{all}", row(&cells(slot)).trim_end());
+    }
+    let best = MeetingSlot::rank(&slots);
+    if best.is_empty() {
+        println!("\nZero slots within working hours");
+        return;
+    }
+    println!("\nBest slots:");
+    for (i, slot) in best.iter().take(3).enumerate() {
+        println!(
+            "{}. {} ({}/{})",
+            i + 1,
+            slot.local
+                .iter()
+                .zip(&names)
+                .map(|(local, name)| format!("{} {name}", local.strftime("%a %H:%M")))
+                .collect::<Vec<String>>()
+                .join(", "),
+            slot.working_count(),
+            zones.len(),
+        );
+    }
+}
+
+fn list_formats(registry: &FormatRegistry, args: &[String], tz: Option<&TimeZone>, input: &Input) {
+    let dtg = match args.first() {
+        Some(arg) => dtg_(arg, input).0,
+        None => Dtg::now(),
+    };
+    for named in registry.list() {
+        let aliases = if named.aliases.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", named.aliases.join(", "))
+        };
+        println!("{}{aliases}: {}", named.name, named.description);
+        for line in dtg
+            .format(&Some(named.format.clone()), &tz.cloned())
+            .lines()
+        {
+            println!("    {line}");
+        }
+    }
+}
+
+/// Format an offset as `+HH:MM[:SS]`
+fn offset_(offset: Offset) -> String {
+    let seconds = offset.seconds();
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.abs();
+    let r = format!("{sign}{:02}:{:02}", seconds / 3600, seconds / 60 % 60);
+    if seconds % 60 == 0 {
+        r
+    } else {
+        format!("{r}:{:02}", seconds % 60)
+    }
+}
+
+fn list_zones(cli: &Cli) {
+    let now = Dtg::now();
+    let search = cli.args.first().map(String::as_str);
+    let offset = cli
+        .offset
+        .as_deref()
+        .and_then(tz_)
+        .map(|x| x.to_offset(Timestamp::now()));
+    let country = cli.country.as_deref();
+    let mut zones = ZoneInfo::list(search, country, offset, &now);
+    if zones.is_empty() {
+        match (search, country, &cli.offset) {
+            (Some(search), _, _) => {
+                error(1, &format!("Zero timezones found matching `{search}`"));
+            }
+            (None, Some(country), _) => {
+                error(1, &format!("Zero timezones found in country `{country}`"));
+            }
+            (None, None, Some(offset)) => {
+                error(1, &format!("Zero timezones found with offset `{offset}`"));
+            }
+            (None, None, None) => {}
+        }
+    }
+    if cli.sort == "offset" {
+        zones.sort_by_key(|x| x.offset);
+    }
+    if !cli.long {
+        for zone in zones {
+            println!("{}", zone.name);
+        }
+        return;
+    }
+    let width = zones.iter().map(|x| x.name.len()).max().unwrap_or(4);
+    println!(
+        "{:width$}  OFFSET     ABBR   DST  NEXT TRANSITION       LOCAL TIME",
+        "NAME",
+    );
+    let local = Some(Format::custom("%Y-%m-%d %H:%M:%S"));
+    for zone in zones {
+        let tz = Some(zone.timezone);
+        println!(
+            "{:width$}  {:9}  {:5}  {:3}  {:20}  {}",
+            zone.name,
+            offset_(zone.offset),
+            zone.abbreviation,
+            if zone.dst { "yes" } else { "no" },
+            zone.next_transition
+                .map_or_else(|| String::from("-"), |x| x.rfc_3339()),
+            now.format(&local, &tz),
+        );
     }
 }
 
 fn tz_(i: &str) -> Option<TimeZone> {
     let t = tz(i);
-    if let Err(ref e) = t {
-        match e.code {
-            101 => error(5, &e.message),
-            102 => error(3, &e.message),
-            _ => error(1, "?"),
+    if let Ok(z) = &t
+        && z.iana_name().is_none()
+    {
+        let mut candidates = tz_abbreviations(i);
+        candidates.dedup_by_key(|x| x.offset);
+        if candidates.len() > 1 {
+            eprintln!(
+                "WARNING: Ambiguous timezone abbreviation `{i}`, using fixed offset {} as in {} \
+                (other candidates: {})!",
+                candidates[0].offset,
+                candidates[0].zone,
+                candidates[1..]
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            );
         }
     }
+    if let Err(ref e) = t {
+        error_(e);
+    }
     t.ok()
 }
+
+fn x_alphabet_(i: Option<&str>) -> XAlphabet {
+    let t = i.map_or_else(|| Ok(XAlphabet::default()), str::parse);
+    if let Err(ref e) = t {
+        error_(e);
+    }
+    t.unwrap()
+}
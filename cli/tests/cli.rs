@@ -4,7 +4,10 @@ CLI Integration Tests
 
 // # Crates
 
-use assert_cmd::{Command, cargo};
+use {
+    assert_cmd::{Command, cargo},
+    std::path::PathBuf,
+};
 
 // # Constants
 
@@ -19,9 +22,35 @@ const PST: &str = "Wed 25 Nov 2020 06:32:37 PST";
 const MONTH: &str = "November";
 const DOW: &str = "Wednesday";
 const X: &str = "XeAOEWb";
+const X_DNS: &str = "1k4aoe0w11";
+const X_DECIMAL: &str = "20201024143237";
+const X_EXTENDED: &str = "XeAOEWb.BSCJqJC";
+const X_EXTENDED_EST: &str = "XeAO9Wb.BSCJqJC-50";
+const X_EXTENDED_IST: &str = "XeAOK2b.BSCJqJC+5U";
 const BCD: &str = "⠄⠄|⣀|⢔|⡐|⡤|⣴";
 const BCD_EST: &str = "⠄⠄|⣀|⢔|⢈|⡤|⣴";
 const BCD_MST: &str = "⠄⠄|⣀|⢔|⢰|⡤|⣴";
+const BCD_CLOCK: &str = "\
+○○○○  ○  ○  ○  ○  ○
+○○○○  ○  ●  ● ○○ ○●
+●○●○  ○ ●○ ○○ ●● ●●
+○○○○ ●● ○● ●○ ●○ ●●";
+const BCD_CLOCK_SQUARES: &str = "\
+□□□□  □  □  □  □  □
+□□□□  □  ■  ■ □□ □■
+■□■□  □ ■□ □□ ■■ ■■
+□□□□ ■■ □■ ■□ ■□ ■■";
+const BINARY_CLOCK_DIGITS: &str = "\
+00
+00       1 1
+11   1 0 0 0
+00 1 1 1 0 0
+11 0 0 1 0 1
+00 1 0 1 0 0
+00 1 1 0 0 1";
+const MILITARY: &str = "251432Z NOV 20";
+const MILITARY_EST: &str = "250932R NOV 20";
+const MILITARY_MST: &str = "250732T NOV 20";
 const COMPACT_DATE: &str = "20201125";
 const COMPACT_TIME: &str = "143237";
 const COMPACT_TIME_EST: &str = "093237";
@@ -32,11 +61,41 @@ const MAX_X: &str = "2kdBTM00";
 const OVERFLOW_SECONDS: &str = "253402207201";
 const OVERFLOW_X: &str = "2kdBTM01";
 
+/// Config file with defaults, named formats, aliases, and a profile
+const CONFIG: &str = "\
+formats = [\"stamp\", \"x\"]
+zones = \"office\"
+separator = \" | \"
+
+[named-formats]
+stamp = \"%Y%m%d %H:%M %Z\"
+
+[aliases]
+office = \"EST5EDT\"
+
+[profiles.team]
+formats = \"dtg\"
+zones = [\"UTC\", \"office\"]
+separator = \"\\t\"
+";
+
 // # Helper functions
 
-/// Retrieve the binary to test
-pub fn cmd() -> Command {
-    Command::new(cargo::cargo_bin!("dtg"))
+/// Retrieve the binary to test (without any config file)
+fn cmd() -> Command {
+    let mut cmd = Command::new(cargo::cargo_bin!("dtg"));
+    cmd.env("XDG_CONFIG_HOME", config_home("none", None));
+    cmd
+}
+
+/// Create a config home directory with an optional `dtg/config.toml` file
+fn config_home(name: &str, config: Option<&str>) -> PathBuf {
+    let home = std::env::temp_dir().join(format!("dtg-test-{name}"));
+    if let Some(config) = config {
+        std::fs::create_dir_all(home.join("dtg")).unwrap();
+        std::fs::write(home.join("dtg").join("config.toml"), config).unwrap();
+    }
+    home
 }
 
 /// Print the command
@@ -47,7 +106,7 @@ fn p(bin: &str, args: &[&str]) {
         args.iter()
             .map(|x| {
                 if x.contains(' ') {
-                    format!("\"{}\"", x)
+                    format!("\"{x}\"")
                 } else {
                     x.to_string()
                 }
@@ -65,7 +124,7 @@ fn fail(bin: &str, args: &[&str], code: i32, msg: &str) {
         .assert()
         .failure()
         .code(code)
-        .stderr(format!("ERROR: {}!\n", msg));
+        .stderr(format!("ERROR: {msg}!\n"));
 }
 
 /// Run command that succeeds
@@ -75,20 +134,25 @@ fn pass(bin: &str, args: &[&str], want: &str) {
         .args(args)
         .assert()
         .success()
-        .stdout(format!("{}\n", want));
+        .stdout(format!("{want}\n"));
 }
 
 /// Compose timestamp
 fn nanoseconds() -> String {
-    format!("{}.{}", SECONDS, NANOSECONDS)
+    format!("{SECONDS}.{NANOSECONDS}")
 }
 
 // # Tests
 
 #[test]
 fn version() {
-    for i in ["-V", "--version"].iter() {
-        pass("dtg", &[i], &format!("dtg {}", env!("CARGO_PKG_VERSION")));
+    for i in &["-V", "--version"] {
+        let output = cmd().args([i]).output().unwrap();
+        assert!(output.status.success());
+        assert!(
+            String::from_utf8_lossy(&output.stdout)
+                .starts_with(&format!("dtg {} (tzdb ", env!("CARGO_PKG_VERSION")))
+        );
     }
 }
 
@@ -102,7 +166,7 @@ fn epoch_subsecond() {
     pass(
         "dtg",
         &["-f", "%Y-%m-%dT%H:%M:%S.%fZ", "--", &nanoseconds()],
-        &RFC3339.replace('Z', &format!(".{}Z", NANOSECONDS)),
+        &RFC3339.replace('Z', &format!(".{NANOSECONDS}Z")),
     );
 }
 
@@ -148,7 +212,7 @@ fn format_custom_day_of_week() {
 
 #[test]
 fn format_a() {
-    let want = format!("{}.{}\n{}\n{}\n{}", SECONDS, NANOSECONDS, RFC3339, UTC, EST);
+    let want = format!("{SECONDS}.{NANOSECONDS}\n{RFC3339}\n{UTC}\n{EST}");
     let ns = nanoseconds();
     pass("dtg", &["-a", "-z", "EST", &ns], &want);
     pass("dtg", &["-z", "EST", "-a", &ns], &want);
@@ -174,11 +238,64 @@ fn format_x() {
 }
 
 #[test]
-fn format_ax() {
-    let want = format!(
-        "{}.{}\n{}\n{}\n{}\n{}",
-        SECONDS, NANOSECONDS, RFC3339, UTC, EST, X
+fn format_x_extended() {
+    let ns = nanoseconds();
+    for i in ["xe", "x-extended"] {
+        pass("dtg", &["-n", i, &ns], X_EXTENDED);
+        pass("dtg", &["-z", "EST5EDT", "-n", i, &ns], X_EXTENDED_EST);
+        pass("dtg", &["-z", "Asia/Kolkata", "-n", i, &ns], X_EXTENDED_IST);
+    }
+    for i in [X_EXTENDED, X_EXTENDED_EST, X_EXTENDED_IST] {
+        pass("dtg", &["-X", "-f", "%s.%f", "--", i], &ns);
+    }
+}
+
+#[test]
+fn format_x_alphabet() {
+    let ns = nanoseconds();
+    pass("dtg", &["-x", "--x-alphabet", "standard", &ns], X);
+    pass("dtg", &["-x", "--x-alphabet", "dns", &ns], X_DNS);
+    pass("dtg", &["-n", "x:dns", &ns], X_DNS);
+    pass(
+        "dtg",
+        &["-n", "x", "--x-alphabet", "0123456789", &ns],
+        X_DECIMAL,
+    );
+    pass("dtg", &["-X", "--x-alphabet", "dns", X_DNS], RFC3339);
+    pass(
+        "dtg",
+        &["-X", "--x-alphabet", "dns", &X_DNS.to_uppercase()],
+        RFC3339,
+    );
+    pass(
+        "dtg",
+        &["-X", "--x-alphabet", "0123456789", X_DECIMAL],
+        RFC3339,
     );
+}
+
+#[test]
+fn from_x_interval() {
+    let want = format!("{RFC3339}/2020-11-25T16:02:37Z");
+    pass("dtg", &["-X", "XeAOEWb/+1U0"], &want);
+    pass("dtg", &["-X", "XeAOEWb/XeAOG2b"], &want);
+    pass("dtg", &["-X", "-n", "x", "XeAOEWb/+1U0"], "XeAOEWb/XeAOG2b");
+    pass(
+        "dtg",
+        &["-X", "-f", "%s", "--", "XeAOEWb/+1U0.U"],
+        "1606314757/1606320157",
+    );
+}
+
+#[test]
+fn format_x_negative_year() {
+    pass("dtg", &["-x", "--", "-63549360000"], "-i2E000");
+    pass("dtg", &["-X", "--", "-i2E000"], "-044-03-15T00:00:00Z");
+}
+
+#[test]
+fn format_ax() {
+    let want = format!("{SECONDS}.{NANOSECONDS}\n{RFC3339}\n{UTC}\n{EST}\n{X}");
     let ns = nanoseconds();
     pass("dtg", &["-a", "-x", "-z", "EST", &ns], &want);
     pass("dtg", &["-a", "-z", "EST", "-x", &ns], &want);
@@ -190,7 +307,7 @@ fn format_ax() {
 
 #[test]
 fn mulitple_f_options() {
-    let want = format!("{}\n{}", MONTH, DOW);
+    let want = format!("{MONTH}\n{DOW}");
     let ns = nanoseconds();
     pass("dtg", &["-f", "%B", "-f", "%A", "-z", "EST", &ns], &want);
     pass(
@@ -215,10 +332,652 @@ fn timezone_search() {
     pass(
         "dtg",
         &["-Z", "new_"],
-        "America/New_York\nAmerica/North_Dakota/New_Salem",
+        "America/New_York\nAmerica/North_Dakota/New_Salem\nCanada/Newfoundland",
+    );
+}
+
+#[test]
+fn timezone_search_fuzzy() {
+    pass("dtg", &["-Z", "newyork"], "America/New_York");
+    pass("dtg", &["-Z", "São Paulo"], "America/Sao_Paulo");
+    pass("dtg", &["-Z", "kolkata"], "Asia/Kolkata");
+}
+
+#[test]
+fn timezone_search_country() {
+    pass("dtg", &["-Z", "--country", "NP"], "Asia/Kathmandu");
+    pass("dtg", &["-Z", "--country", "NO"], "Europe/Oslo");
+    pass(
+        "dtg",
+        &["-Z", "--country", "br", "sao"],
+        "America/Sao_Paulo",
+    );
+}
+
+#[test]
+fn timezone_fuzzy() {
+    let ns = nanoseconds();
+    pass("dtg", &["-z", "new york", &ns], EST);
+    pass("dtg", &["-z", "UTC,newyork", &ns], &[UTC, EST].join("\n"));
+}
+
+#[test]
+fn timezone_search_offset() {
+    pass(
+        "dtg",
+        &["-Z", "--offset", "+05:45"],
+        "Asia/Kathmandu\nAsia/Katmandu",
+    );
+    pass(
+        "dtg",
+        &["-Z", "--offset", "+0545", "kath"],
+        "Asia/Kathmandu",
+    );
+    pass("dtg", &["-Z", "--sort", "offset", "omeo"], "Romeo");
+}
+
+#[test]
+fn timezone_search_long() {
+    let output = cmd().args(["-Z", "--long", "kathmandu"]).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines = stdout.lines().collect::<Vec<&str>>();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("NAME            OFFSET     ABBR   DST  NEXT TRANSITION"));
+    assert!(lines[1].starts_with("Asia/Kathmandu  +05:45     +0545  no   -"));
+}
+
+#[test]
+fn timezone_search_military() {
+    pass("dtg", &["-Z", "omeo"], "Romeo");
+}
+
+#[test]
+fn timezone_military() {
+    let ns = nanoseconds();
+    for i in ["R", "r", "Romeo", "ROMEO"] {
+        pass("dtg", &["-z", i, "-n", "dtg", &ns], "250932R NOV 20");
+    }
+    pass("dtg", &["-z", "Z", &ns], UTC);
+    pass("dtg", &["-z", "Zulu", &ns], UTC);
+    pass("dtg", &["-z", "M", "-f", "%H%M %:z", &ns], "0232 +12:00");
+    pass("dtg", &["-z", "Y", "-f", "%H%M %:z", &ns], "0232 -12:00");
+}
+
+#[test]
+fn timezone_offset() {
+    let ns = nanoseconds();
+    for i in ["-05:00", "-0500", "-5", "UTC-5", "GMT-05:00", "utc-5"] {
+        pass("dtg", &["-z", i, "-f", "%H%M %:z", &ns], "0932 -05:00");
+    }
+    pass(
+        "dtg",
+        &["-z", "+05:30", "-f", "%H%M %:z", &ns],
+        "2002 +05:30",
+    );
+    pass(
+        "dtg",
+        &["-z", "UTC+3", "-f", "%H%M %:z", &ns],
+        "1732 +03:00",
+    );
+}
+
+#[test]
+fn timezone_posix() {
+    let ns = nanoseconds();
+    pass("dtg", &["-z", "EST5EDT,M3.2.0,M11.1.0", &ns], EST);
+    pass(
+        "dtg",
+        &["-z", "UTC,EST5EDT,M3.2.0,M11.1.0,MST7MDT", &ns],
+        &[UTC, EST, MST].join("\n"),
+    );
+}
+
+#[test]
+fn timezone_abbreviation() {
+    let ns = nanoseconds();
+    pass("dtg", &["-z", "AEST", &ns], "Thu 26 Nov 2020 00:32:37 AEST");
+    pass("dtg", &["-z", "IST", &ns], "Wed 25 Nov 2020 20:02:37 IST");
+    pass(
+        "dtg",
+        &["-z", "CET,EET", "1658448142"],
+        "Fri 22 Jul 2022 01:02:22 CET\nFri 22 Jul 2022 02:02:22 EET",
+    );
+    let output = cmd().args(["-z", "CST", &ns]).output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), format!("{CST}\n"));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with(
+        "WARNING: Ambiguous timezone abbreviation `CST`, using fixed offset -06 as in \
+        America/Chicago (other candidates: ",
+    ));
+}
+
+#[test]
+fn transitions() {
+    pass(
+        "dtg",
+        &[
+            "--transitions",
+            "America/New_York",
+            "1640995200",
+            "1672531200",
+        ],
+        "\
+2022-03-13T07:00:00Z -05:00 EST -> -04:00 EDT (DST)
+2022-11-06T06:00:00Z -04:00 EDT -> -05:00 EST",
+    );
+    pass(
+        "dtg",
+        &[
+            "--transitions",
+            "EST5EDT",
+            "-n",
+            "dtg",
+            "-z",
+            "MST7MDT",
+            SECONDS,
+        ],
+        "\
+140000T MAR 21 -05:00 EST -> -04:00 EDT (DST)
+070000S NOV 21 -04:00 EDT -> -05:00 EST",
+    );
+    pass(
+        "dtg",
+        &["-X", "--transitions", "Europe/London", "XeAOEWb/XfAOEWb"],
+        "\
+2021-03-28T01:00:00Z +00:00 GMT -> +01:00 BST (DST)
+2021-10-31T01:00:00Z +01:00 BST -> +00:00 GMT",
+    );
+}
+
+#[test]
+fn plan() {
+    pass(
+        "dtg",
+        &[
+            "--plan",
+            "-z",
+            "UTC,EST5EDT",
+            "--hours",
+            "9-17,8-18",
+            SECONDS,
+        ],
+        "\
+UTC         EST5EDT
+Wed 00:00   Tue 19:00
+Wed 01:00   Tue 20:00
+Wed 02:00   Tue 21:00
+Wed 03:00   Tue 22:00
+Wed 04:00   Tue 23:00
+Wed 05:00   Wed 00:00
+Wed 06:00   Wed 01:00
+Wed 07:00   Wed 02:00
+Wed 08:00   Wed 03:00
+Wed 09:00*  Wed 04:00
+Wed 10:00*  Wed 05:00
+Wed 11:00*  Wed 06:00
+Wed 12:00*  Wed 07:00
+Wed 13:00*  Wed 08:00*  <= all
+Wed 14:00*  Wed 09:00*  <= all
+Wed 15:00*  Wed 10:00*  <= all
+Wed 16:00*  Wed 11:00*  <= all
+Wed 17:00   Wed 12:00*
+Wed 18:00   Wed 13:00*
+Wed 19:00   Wed 14:00*
+Wed 20:00   Wed 15:00*
+Wed 21:00   Wed 16:00*
+Wed 22:00   Wed 17:00*
+Wed 23:00   Wed 18:00
+
+Best slots:
+1. Wed 13:00 UTC, Wed 08:00 EST5EDT (2/2)
+2. Wed 14:00 UTC, Wed 09:00 EST5EDT (2/2)
+3. Wed 15:00 UTC, Wed 10:00 EST5EDT (2/2)",
+    );
+    pass(
+        "dtg",
+        &[
+            "--plan",
+            "-z",
+            "UTC,Asia/Tokyo",
+            "--hours",
+            "9-17,22-06",
+            SECONDS,
+        ],
+        "\
+UTC         Asia/Tokyo
+Wed 00:00   Wed 09:00
+Wed 01:00   Wed 10:00
+Wed 02:00   Wed 11:00
+Wed 03:00   Wed 12:00
+Wed 04:00   Wed 13:00
+Wed 05:00   Wed 14:00
+Wed 06:00   Wed 15:00
+Wed 07:00   Wed 16:00
+Wed 08:00   Wed 17:00
+Wed 09:00*  Wed 18:00
+Wed 10:00*  Wed 19:00
+Wed 11:00*  Wed 20:00
+Wed 12:00*  Wed 21:00
+Wed 13:00*  Wed 22:00*  <= all
+Wed 14:00*  Wed 23:00*  <= all
+Wed 15:00*  Thu 00:00*  <= all
+Wed 16:00*  Thu 01:00*  <= all
+Wed 17:00   Thu 02:00*
+Wed 18:00   Thu 03:00*
+Wed 19:00   Thu 04:00*
+Wed 20:00   Thu 05:00*
+Wed 21:00   Thu 06:00
+Wed 22:00   Thu 07:00
+Wed 23:00   Thu 08:00
+
+Best slots:
+1. Wed 13:00 UTC, Wed 22:00 Asia/Tokyo (2/2)
+2. Wed 14:00 UTC, Wed 23:00 Asia/Tokyo (2/2)
+3. Wed 15:00 UTC, Thu 00:00 Asia/Tokyo (2/2)",
+    );
+}
+
+#[test]
+fn invalid_plan() {
+    fail(
+        "dtg",
+        &["--plan", "--hours", "9-9"],
+        10,
+        "Invalid working hours: `9-9`",
+    );
+    fail(
+        "dtg",
+        &["--plan", "--hours", "9-17,8-16"],
+        10,
+        "More working hours than timezones",
+    );
+    fail(
+        "dtg",
+        &["--plan", "2020-13-01"],
+        2,
+        "Invalid timestamp: `2020-13-01`",
+    );
+}
+
+#[test]
+fn json() {
+    pass(
+        "dtg",
+        &[
+            "--json",
+            "-z",
+            "UTC,EST5EDT",
+            "-n",
+            "x",
+            "-f",
+            "%s",
+            "1606314757.5",
+        ],
+        concat!(
+            r#"[{"input":"1606314757.5","seconds":1606314757,"nanoseconds":500000000,"zones":["#,
+            r#"{"zone":"UTC","rfc_3339":"2020-11-25T14:32:37.5+00:00","offset":"+00:00","#,
+            r#""abbreviation":"UTC","dst":false,"formats":[{"format":"%s","value":"1606314757"},"#,
+            r#"{"format":"x","value":"XeAOEWb"}]},"#,
+            r#"{"zone":"EST5EDT","rfc_3339":"2020-11-25T09:32:37.5-05:00","offset":"-05:00","#,
+            r#""abbreviation":"EST","dst":false,"formats":[{"format":"%s","value":"1606314757"},"#,
+            r#"{"format":"x","value":"XeAOEWb"}]}]}]"#,
+        ),
+    );
+    pass(
+        "dtg",
+        &[
+            "--ndjson",
+            "-X",
+            "-z",
+            "UTC",
+            "-n",
+            "a",
+            "XeAOEWb/XeAOEWc",
+            "XeAOEWd",
+        ],
+        concat!(
+            r#"{"input":"XeAOEWb/XeAOEWc","seconds":1606314757,"nanoseconds":0,"zones":["#,
+            r#"{"zone":"UTC","rfc_3339":"2020-11-25T14:32:37+00:00","offset":"+00:00","#,
+            r#""abbreviation":"UTC","dst":false,"formats":[{"format":"a","value":"1606314757.000000000\n"#,
+            r#"2020-11-25T14:32:37Z\nWed 25 Nov 2020 14:32:37 UTC\nWed 25 Nov 2020 14:32:37 UTC"}]}],"#,
+            r#""end":{"seconds":1606314758,"nanoseconds":0,"zones":["#,
+            r#"{"zone":"UTC","rfc_3339":"2020-11-25T14:32:38+00:00","offset":"+00:00","#,
+            r#""abbreviation":"UTC","dst":false,"formats":[{"format":"a","value":"1606314758.000000000\n"#,
+            r#"2020-11-25T14:32:38Z\nWed 25 Nov 2020 14:32:38 UTC\nWed 25 Nov 2020 14:32:38 UTC"}]}]}}"#,
+            "\n",
+            r#"{"input":"XeAOEWd","seconds":1606314759,"nanoseconds":0,"zones":["#,
+            r#"{"zone":"UTC","rfc_3339":"2020-11-25T14:32:39+00:00","offset":"+00:00","#,
+            r#""abbreviation":"UTC","dst":false,"formats":[{"format":"a","value":"1606314759.000000000\n"#,
+            r#"2020-11-25T14:32:39Z\nWed 25 Nov 2020 14:32:39 UTC\nWed 25 Nov 2020 14:32:39 UTC"}]}]}"#,
+        ),
+    );
+    pass(
+        "dtg",
+        &["--ndjson", "-n", "x", "-f", "x", "1606314757"],
+        concat!(
+            r#"{"input":"1606314757","seconds":1606314757,"nanoseconds":0,"zones":["#,
+            r#"{"zone":"UTC","rfc_3339":"2020-11-25T14:32:37+00:00","offset":"+00:00","#,
+            r#""abbreviation":"UTC","dst":false,"formats":[{"format":"x","value":"x"},"#,
+            r#"{"format":"x","value":"XeAOEWb"}]}]}"#,
+        ),
+    );
+    fail(
+        "dtg",
+        &["--json", "--ndjson"],
+        6,
+        "Options `--json` and `--ndjson` are mutually exclusive",
+    );
+    fail(
+        "dtg",
+        &["--json", "-i", "1"],
+        6,
+        "Options `--json` and `-i` are mutually exclusive (use `--ndjson`)",
+    );
+}
+
+#[test]
+fn csv() {
+    pass(
+        "dtg",
+        &[
+            "--csv",
+            "-z",
+            "UTC,EST5EDT",
+            "-n",
+            "a",
+            "-f",
+            "%d, %b \"%y\"",
+            SECONDS,
+        ],
+        &format!(
+            "\
+\"%d, %b \"\"%y\"\" (UTC)\",\"%d, %b \"\"%y\"\" (EST5EDT)\",a (UTC),a (EST5EDT)
+\"25, Nov \"\"20\"\"\",\"25, Nov \"\"20\"\"\",\"{SECONDS}.000000000
+{RFC3339}
+{UTC}
+{UTC}\",\"{SECONDS}.000000000
+{RFC3339}
+{UTC}
+{EST}\"",
+        ),
+    );
+    pass(
+        "dtg",
+        &[
+            "--tsv",
+            "-X",
+            "-z",
+            "UTC",
+            "-n",
+            "x",
+            "-f",
+            "%d, %b",
+            "XeAOEWb/XeAOEWc",
+            "XeAOEWd",
+        ],
+        "\
+%d, %b (UTC)\tx (UTC)
+25, Nov/25, Nov\tXeAOEWb/XeAOEWc
+25, Nov\tXeAOEWd",
+    );
+    fail(
+        "dtg",
+        &["--csv", "--tsv", "--json"],
+        6,
+        "Options `--json` and `--csv` and `--tsv` are mutually exclusive",
+    );
+}
+
+#[test]
+fn table() {
+    pass(
+        "dtg",
+        &[
+            "--table",
+            "-z",
+            "UTC,EST5EDT",
+            "-f",
+            "%H:%M",
+            "-n",
+            "x",
+            SECONDS,
+        ],
+        "\
+┌─────────┬───────┬─────────┐
+│ ZONE    │ %H:%M │ x       │
+├─────────┼───────┼─────────┤
+│ UTC     │ 14:32 │ XeAOEWb │
+│ EST5EDT │ 09:32 │ XeAOEWb │
+└─────────┴───────┴─────────┘",
+    );
+    pass(
+        "dtg",
+        &[
+            "--table",
+            "--table-style",
+            "ascii",
+            "--transpose",
+            "-z",
+            "UTC,EST5EDT",
+            "-a",
+            "-n",
+            "x",
+            SECONDS,
+        ],
+        &format!(
+            "\
++--------+------------------------------+------------------------------+
+| FORMAT | UTC                          | EST5EDT                      |
++--------+------------------------------+------------------------------+
+| a      | {SECONDS}.000000000         | {SECONDS}.000000000         |
+|        | {RFC3339}         | {RFC3339}         |
+|        | {UTC} | {UTC} |
+|        | {UTC} | {EST} |
++--------+------------------------------+------------------------------+
+| x      | XeAOEWb                      | XeAOEWb                      |
++--------+------------------------------+------------------------------+",
+        ),
+    );
+    pass(
+        "dtg",
+        &[
+            "--table",
+            "--table-style",
+            "ascii",
+            "--color",
+            "always",
+            "-z",
+            "UTC,local",
+            "-f",
+            "%s",
+            SECONDS,
+        ],
+        &format!(
+            "\
++-------+------------+
+| ZONE  | %s         |
++-------+------------+
+| UTC   | {SECONDS} |
+| \x1b[1;32mlocal\x1b[0m | \x1b[1;32m{SECONDS}\x1b[0m |
++-------+------------+",
+        ),
+    );
+    pass(
+        "dtg",
+        &["-f", "%s", "--table", SECONDS],
+        &format!(
+            "\
+┌──────┬────────────┐
+│ ZONE │ %s         │
+├──────┼────────────┤
+│ UTC  │ {SECONDS} │
+└──────┴────────────┘",
+        ),
+    );
+    fail(
+        "dtg",
+        &["--table", "--csv"],
+        6,
+        "Options `--csv` and `--table` are mutually exclusive",
+    );
+}
+
+#[test]
+fn csv_column() {
+    let csv = "\
+id,when,\"note, x\"
+1,2020-11-25 09:32,\"a \"\"b\"\"\"
+2,bad,\"multi
+line\"
+3
+4,2020-11-26 00:00,z
+";
+    cmd()
+        .args(["--csv-column", "when", "--from-format", "%Y-%m-%d %H:%M"])
+        .args(["-z", "EST5EDT", "-f", "%s"])
+        .write_stdin(csv)
+        .assert()
+        .failure()
+        .code(2)
+        .stdout(
+            "\
+id,when,\"note, x\"
+1,1606314720,\"a \"\"b\"\"\"
+2,bad,\"multi
+line\"
+3
+4,1606366800,z
+",
+        )
+        .stderr(
+            "\
+WARNING: Line 3: Invalid timestamp: `bad`: strptime parsing failed: %Y failed: failed to parse \
+year: invalid number, no digits found!
+WARNING: Line 5: Missing column `when`!
+ERROR: Failed to convert 2 row(s)!
+",
+        );
+    cmd()
+        .args([
+            "--tsv",
+            "--csv-column",
+            "1",
+            "--append",
+            "-z",
+            "UTC,EST5EDT",
+            "-n",
+            "x",
+        ])
+        .write_stdin(format!("a\tb\n{SECONDS}\tq\n"))
+        .assert()
+        .success()
+        .stdout(format!(
+            "a\tb\tx (UTC)\tx (EST5EDT)\n{SECONDS}\tq\tXeAOEWb\tXeAOEWb\n"
+        ));
+    cmd()
+        .args(["--csv-column", "c"])
+        .write_stdin("a,b\n")
+        .assert()
+        .failure()
+        .code(1)
+        .stderr("ERROR: Column `c` not found!\n");
+    cmd()
+        .args(["--csv-column", "a", "-f", "%s"])
+        .write_stdin(&b"a,b\n1606314757,x\n\xff,y\n1606314758,z\n"[..])
+        .assert()
+        .failure()
+        .code(2)
+        .stdout(&b"a,b\n1606314757,x\n\xff,y\n1606314758,z\n"[..])
+        .stderr("WARNING: Line 3: Invalid UTF-8!\nERROR: Failed to convert 1 row(s)!\n");
+    cmd()
+        .args(["--csv-column", "a"])
+        .write_stdin(&b"\xff\n"[..])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr("ERROR: Line 1: Invalid UTF-8!\n");
+}
+
+#[test]
+fn from_format() {
+    pass(
+        "dtg",
+        &[
+            "--from-format",
+            "%Y-%m-%d %H:%M:%S",
+            "-z",
+            "EST5EDT",
+            "2020-11-25 09:32:37",
+        ],
+        EST,
+    );
+    fail(
+        "dtg",
+        &["--from-format", "%Y-%m-%d", "-X"],
+        6,
+        "Options `-X` and `--from-format` are mutually exclusive",
+    );
+    fail(
+        "dtg",
+        &["--from-format", "%Y-%m-%d", "25/11/2020"],
+        2,
+        "Invalid timestamp: `25/11/2020`: strptime parsing failed: expected to match literal \
+        byte `-` from format string, but found byte `/` in input",
+    );
+}
+
+#[test]
+fn timezone_tzif() {
+    pass(
+        "dtg",
+        &["-z", "/usr/share/zoneinfo/America/New_York", &nanoseconds()],
+        EST,
     );
 }
 
+#[test]
+fn tzdb() {
+    pass(
+        "dtg",
+        &[
+            "--tzdb",
+            "/usr/share/zoneinfo",
+            "-z",
+            "EST5EDT",
+            &nanoseconds(),
+        ],
+        EST,
+    );
+}
+
+#[test]
+fn timezone_alias() {
+    let home = config_home(
+        "alias",
+        Some(
+            "\
+[aliases]
+office = \"EST5EDT\"
+team = [\"UTC\", \"Office\", \"MST7MDT\"]
+",
+        ),
+    );
+    let ns = nanoseconds();
+    cmd()
+        .env("XDG_CONFIG_HOME", &home)
+        .args(["-z", "office", &ns])
+        .assert()
+        .success()
+        .stdout(format!("{EST}\n"));
+    cmd()
+        .env("XDG_CONFIG_HOME", &home)
+        .args(["-z", "team,CST6CDT", &ns])
+        .assert()
+        .success()
+        .stdout(format!("{UTC}\n{EST}\n{MST}\n{CST}\n"));
+}
+
 #[test]
 fn max_seconds() {
     pass("dtg", &[MAX_SECONDS], MAX);
@@ -231,14 +990,14 @@ fn max_x() {
 
 #[test]
 fn named_format_all() {
-    let want = format!("{}.{}\n{}\n{}\n{}", SECONDS, NANOSECONDS, RFC3339, UTC, UTC);
-    let want_est = format!("{}.{}\n{}\n{}\n{}", SECONDS, NANOSECONDS, RFC3339, UTC, EST);
-    let want_mst = format!("{}.{}\n{}\n{}\n{}", SECONDS, NANOSECONDS, RFC3339, UTC, MST);
+    let want = format!("{SECONDS}.{NANOSECONDS}\n{RFC3339}\n{UTC}\n{UTC}");
+    let eastern = format!("{SECONDS}.{NANOSECONDS}\n{RFC3339}\n{UTC}\n{EST}");
+    let mountain = format!("{SECONDS}.{NANOSECONDS}\n{RFC3339}\n{UTC}\n{MST}");
     let ns = nanoseconds();
     for i in ["a", "all"] {
         pass("dtg", &["-n", i, &ns], &want);
-        pass("dtg", &["-z", "EST5EDT", "-n", i, &ns], &want_est);
-        pass("dtg", &["-z", "MST7MDT", "-n", i, &ns], &want_mst);
+        pass("dtg", &["-z", "EST5EDT", "-n", i, &ns], &eastern);
+        pass("dtg", &["-z", "MST7MDT", "-n", i, &ns], &mountain);
     }
 }
 
@@ -250,6 +1009,60 @@ fn named_format_bcd() {
     pass("dtg", &["-z", "MST7MDT", "-n", "bcd", &ns], BCD_MST);
 }
 
+#[test]
+fn from_bcd() {
+    pass("dtg", &["-B", BCD], RFC3339);
+    pass("dtg", &["-B", "-f", "%s", "--", BCD], SECONDS);
+    pass("dtg", &["-B", "-z", "EST5EDT", BCD_EST], EST);
+    pass(
+        "dtg",
+        &["-B", "-z", "MST7MDT", "-n", "bcd", BCD_MST],
+        BCD_MST,
+    );
+}
+
+#[test]
+fn from_military() {
+    pass("dtg", &["-M", MILITARY], "2020-11-25T14:32:00Z");
+    pass("dtg", &["-M", "-f", "%s", MILITARY_EST], "1606314720");
+    pass("dtg", &["-M", "-n", "dtg", "251432znov20"], MILITARY);
+    pass(
+        "dtg",
+        &["-M", "-z", "MST7MDT", "-n", "dtg", MILITARY_EST],
+        MILITARY_MST,
+    );
+}
+
+#[test]
+fn named_format_dtg() {
+    let ns = nanoseconds();
+    pass("dtg", &["-n", "dtg", &ns], MILITARY);
+    pass("dtg", &["-z", "EST5EDT", "-n", "dtg", &ns], MILITARY_EST);
+    pass("dtg", &["-z", "MST7MDT", "-n", "dtg", &ns], MILITARY_MST);
+    pass("dtg", &["-z", "Asia/Kolkata", "-n", "dtg", &ns], MILITARY);
+}
+
+#[test]
+fn named_format_bcd_clock() {
+    let ns = nanoseconds();
+    pass("dtg", &["-n", "bcd-clock", &ns], BCD_CLOCK);
+    pass(
+        "dtg",
+        &["-n", "bcd-clock", "--glyphs", "squares", &ns],
+        BCD_CLOCK_SQUARES,
+    );
+}
+
+#[test]
+fn named_format_binary_clock() {
+    let ns = nanoseconds();
+    pass(
+        "dtg",
+        &["-n", "binary-clock", "--glyphs", "digits", &ns],
+        BINARY_CLOCK_DIGITS,
+    );
+}
+
 #[test]
 fn named_format_compact_date() {
     let ns = nanoseconds();
@@ -264,12 +1077,12 @@ fn named_format_compact_date() {
 fn named_format_compact_date_time() {
     let ns = nanoseconds();
     let want = format!("{COMPACT_DATE}-{COMPACT_TIME}");
-    let want_est = format!("{COMPACT_DATE}-{COMPACT_TIME_EST}");
-    let want_mst = format!("{COMPACT_DATE}-{COMPACT_TIME_MST}");
+    let eastern = format!("{COMPACT_DATE}-{COMPACT_TIME_EST}");
+    let mountain = format!("{COMPACT_DATE}-{COMPACT_TIME_MST}");
     for i in ["cdt", "compact-date-time"] {
         pass("dtg", &["-n", i, &ns], &want);
-        pass("dtg", &["-z", "EST5EDT", "-n", i, &ns], &want_est);
-        pass("dtg", &["-z", "MST7MDT", "-n", i, &ns], &want_mst);
+        pass("dtg", &["-z", "EST5EDT", "-n", i, &ns], &eastern);
+        pass("dtg", &["-z", "MST7MDT", "-n", i, &ns], &mountain);
     }
 }
 
@@ -315,6 +1128,281 @@ fn timezone_search_found_zero() {
     );
 }
 
+#[test]
+fn timezone_search_country_found_zero() {
+    fail(
+        "dtg",
+        &["-Z", "--country", "XX"],
+        1,
+        "Zero timezones found in country `XX`",
+    );
+}
+
+#[test]
+fn timezone_search_offset_found_zero() {
+    fail(
+        "dtg",
+        &["-Z", "--offset", "+05:47"],
+        1,
+        "Zero timezones found with offset `+05:47`",
+    );
+}
+
+#[test]
+fn invalid_tzdb() {
+    fail(
+        "dtg",
+        &["--tzdb", "nonexistent"],
+        8,
+        "Invalid timezone database: `nonexistent`",
+    );
+    cmd()
+        .env("DTG_TZDB", "nonexistent")
+        .arg("-Z")
+        .assert()
+        .failure()
+        .code(8)
+        .stderr("ERROR: Invalid timezone database: `nonexistent`!\n");
+    cmd()
+        .env("DTG_TZDB", "nonexistent")
+        .args(["--tzdb", "system", SECONDS])
+        .assert()
+        .success()
+        .stdout(format!("{RFC3339}\n"));
+}
+
+#[test]
+fn config_defaults() {
+    let home = config_home("defaults", Some(CONFIG));
+    let run = |args: &[&str], want: &str| {
+        cmd()
+            .env("XDG_CONFIG_HOME", &home)
+            .args(args)
+            .assert()
+            .success()
+            .stdout(format!("{want}\n"));
+    };
+    run(&[SECONDS], "20201125 09:32 EST | XeAOEWb");
+    let local = cmd()
+        .env("XDG_CONFIG_HOME", &home)
+        .args(["-z", "local", SECONDS])
+        .output()
+        .unwrap();
+    run(
+        &["-l", SECONDS],
+        String::from_utf8(local.stdout).unwrap().trim_end(),
+    );
+    run(&["-n", "stamp", "-z", "UTC", SECONDS], "20201125 14:32 UTC");
+    run(&["-s", ",", "-f", "%H:%M", SECONDS], "09:32");
+    run(
+        &["--profile", "team", SECONDS],
+        "251432Z NOV 20\t250932R NOV 20",
+    );
+    cmd()
+        .env("XDG_CONFIG_HOME", &home)
+        .args(["--profile", "solo"])
+        .assert()
+        .failure()
+        .code(9)
+        .stderr(format!(
+            "ERROR: Invalid config file `{}`: Profile `solo` not found!\n",
+            home.join("dtg").join("config.toml").display(),
+        ));
+}
+
+#[test]
+fn config_dump() {
+    let home = config_home("dump", Some(CONFIG));
+    let run = |args: &[&str], want: &str| {
+        cmd()
+            .env("XDG_CONFIG_HOME", &home)
+            .args(args)
+            .assert()
+            .success()
+            .stdout(format!("{want}\n"));
+    };
+    run(
+        &["--profile", "team", "--config-dump"],
+        &format!(
+            "\
+# {} (profile: team)
+formats = [\"dtg\"]
+zones = [\"UTC\", \"office\"]
+separator = \"\\t\"
+
+[named-formats]
+stamp = \"%Y%m%d %H:%M %Z\"
+
+[aliases]
+office = [\"EST5EDT\"]",
+            home.join("dtg").join("config.toml").display(),
+        ),
+    );
+    run(
+        &["--config-dump"],
+        &format!(
+            "\
+# {}
+formats = [\"stamp\", \"x\"]
+zones = [\"office\"]
+separator = \" | \"
+
+[named-formats]
+stamp = \"%Y%m%d %H:%M %Z\"
+
+[aliases]
+office = [\"EST5EDT\"]",
+            home.join("dtg").join("config.toml").display(),
+        ),
+    );
+    run(
+        &["-l", "--config-dump"],
+        &format!(
+            "\
+# {}
+formats = [\"stamp\", \"x\"]
+zones = [\"local\"] # -l
+separator = \" | \"
+
+[named-formats]
+stamp = \"%Y%m%d %H:%M %Z\"
+
+[aliases]
+office = [\"EST5EDT\"]",
+            home.join("dtg").join("config.toml").display(),
+        ),
+    );
+    pass(
+        "dtg",
+        &["--config-dump"],
+        "\
+# No config file
+formats = [\"rfc-3339\"] # default
+zones = [\"UTC\"] # default
+separator = \"\\\\n\" # default",
+    );
+    pass(
+        "dtg",
+        &["-a", "--config-dump"],
+        "\
+# No config file
+formats = [\"a\"] # -a
+zones = [\"local\"] # default
+separator = \"\\\\n\" # default",
+    );
+    let home = config_home("partial", Some("zones = \"UTC\"\n"));
+    cmd()
+        .env("XDG_CONFIG_HOME", &home)
+        .arg("--config-dump")
+        .assert()
+        .success()
+        .stdout(format!(
+            "\
+# {}
+formats = [\"default\"] # default
+zones = [\"UTC\"]
+separator = \"\\\\n\" # default
+",
+            home.join("dtg").join("config.toml").display(),
+        ));
+}
+
+#[test]
+fn list_formats() {
+    let home = config_home(
+        "formats",
+        Some("[named-formats]\nstamp = \"%Y%m%d %H:%M\"\n"),
+    );
+    cmd()
+        .env("XDG_CONFIG_HOME", &home)
+        .args([
+            "--list-formats",
+            "-z",
+            "EST5EDT",
+            "--glyphs",
+            "digits",
+            "1606314757.5",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            "\
+all (a): \"a\" format: epoch, RFC 3339 / UTC, default / UTC, and default / timezone
+    1606314757.500000000
+    2020-11-25T14:32:37Z
+    Wed 25 Nov 2020 14:32:37 UTC
+    Wed 25 Nov 2020 09:32:37 EST
+bcd: \"bcd\" format: binary clock with Braille Patterns
+    ⠄⠄|⣀|⢔|⢈|⡤|⣴
+bcd-clock: Binary clock with one column per digit
+    0000  0  0  1  0  0
+    0000  0  1  0 00 01
+    1010  0 10 00 11 11
+    0000 11 01 01 10 11
+binary-clock: Binary clock with one column per field
+    00
+    00       1 1
+    11   1 0 0 0
+    00 1 1 1 0 0
+    11 0 0 0 0 1
+    00 1 0 0 0 0
+    00 1 1 1 0 1
+compact-date (cd): %Y%m%d
+    20201125
+compact-date-time (cdt): %Y%m%d-%H%M%S
+    20201125-093237
+compact-time (ct): %H%M%S
+    093237
+default (d): %a %d %b %Y %H:%M:%S %Z
+    Wed 25 Nov 2020 09:32:37 EST
+dtg: Military date time group (DDHHMMZ MON YY)
+    250932R NOV 20
+rfc-3339 (i, r, iso, rfc): %Y-%m-%dT%H:%M:%SZ
+    2020-11-25T14:32:37Z
+x: \"x\" format: novel UTC / base 60 encoding
+    XeAOEWb
+x-extended (xe): Extended \"x\" format: timezone, fractional seconds, and offset
+    XeAO9Wb.U-50
+stamp: %Y%m%d %H:%M
+    20201125 09:32
+",
+        );
+}
+
+#[test]
+fn invalid_config() {
+    let home = config_home("invalid", Some("aliases = 3\n"));
+    cmd()
+        .env("XDG_CONFIG_HOME", &home)
+        .assert()
+        .failure()
+        .code(9)
+        .stderr(format!(
+            "ERROR: Invalid config file `{}`: `aliases` is not a table!\n",
+            home.join("dtg").join("config.toml").display(),
+        ));
+    let home = config_home("builtin", Some("[named-formats]\ncd = \"%F\"\n"));
+    cmd()
+        .env("XDG_CONFIG_HOME", &home)
+        .assert()
+        .failure()
+        .code(9)
+        .stderr(format!(
+            "ERROR: Invalid config file `{}`: Invalid named format: `cd` is empty or built-in!\n",
+            home.join("dtg").join("config.toml").display(),
+        ));
+    let home = config_home("specifier", Some("[named-formats]\nstamp = \"%Y%K\"\n"));
+    cmd()
+        .env("XDG_CONFIG_HOME", &home)
+        .assert()
+        .failure()
+        .code(9)
+        .stderr(format!(
+            "ERROR: Invalid config file `{}`: Invalid format specifier `%K` at position 2: `%Y%K`!\n",
+            home.join("dtg").join("config.toml").display(),
+        ));
+}
+
 #[test]
 fn invalid_argument() {
     fail("dtg", &["blah"], 2, "Invalid timestamp: `blah`");
@@ -322,7 +1410,10 @@ fn invalid_argument() {
 
 #[test]
 fn invalid_time_zone() {
-    fail("dtg", &["-z", "Z"], 3, "Invalid timezone: `Z`");
+    fail("dtg", &["-z", "ZZ"], 3, "Invalid timezone: `ZZ`");
+    for i in ["+053", "+5:3", "+26", "UTC+", "EST5EDT,M3.2.0"] {
+        fail("dtg", &["-z", i], 3, &format!("Invalid timezone: `{i}`"));
+    }
 }
 
 #[test]
@@ -331,7 +1422,7 @@ fn overflow_seconds() {
         "dtg",
         &[OVERFLOW_SECONDS],
         2,
-        &format!("Invalid timestamp: `{}`", OVERFLOW_SECONDS),
+        &format!("Invalid timestamp: `{OVERFLOW_SECONDS}`"),
     );
 }
 
@@ -341,7 +1432,111 @@ fn overflow_x() {
         "dtg",
         &["-X", OVERFLOW_X],
         2,
-        &format!("Invalid timestamp: `{}`", OVERFLOW_X),
+        &format!(
+            "Invalid timestamp: `{OVERFLOW_X}`: converting datetime with time zone offset `+00` to \
+            timestamp overflowed: parameter 'Unix timestamp seconds' is not in the required \
+            range of -377705023201..=253402207200",
+        ),
+    );
+}
+
+#[test]
+fn invalid_bcd() {
+    for i in ["⠄⠄|⣀|⢔|⡐|⡤", "⠄⠄|⣀|⢔|⡐|⡤|⣴|⣴"] {
+        fail("dtg", &["-B", i], 2, &format!("Invalid timestamp: `{i}`"));
+    }
+    fail(
+        "dtg",
+        &["-B", "⠄⠄|⣠|⢔|⡐|⡤|⣴"],
+        2,
+        "Invalid \"bcd\" format month: 13 (expected 1-12)",
+    );
+    fail(
+        "dtg",
+        &["-B", "⠄⠄|⣀|⢔|⡐|⡤|x"],
+        2,
+        "Failed to parse a \"bcd\" format second char at position 11: `⠄⠄|⣀|⢔|⡐|⡤|x`",
+    );
+}
+
+#[test]
+fn invalid_military() {
+    for i in ["251432Z NOV", "251432Z NOP 20", "321432Z NOV 20"] {
+        fail("dtg", &["-M", i], 2, &format!("Invalid timestamp: `{i}`"));
+    }
+    fail(
+        "dtg",
+        &["-M", "251432J NOV 20"],
+        2,
+        "Invalid military time zone letter: `J`",
+    );
+}
+
+#[test]
+fn invalid_x_interval() {
+    for (i, message) in [
+        ("XeAOEWb/", "Invalid timestamp: ``"),
+        ("XeAOEWb/+", "Invalid duration: ``"),
+        ("XeAOEWb/1U0", "Invalid timestamp: `1U0`"),
+        ("XeAOEWb/+1U0.U0", "Invalid duration: `1U0.U0`"),
+    ] {
+        fail("dtg", &["-X", i], 2, message);
+    }
+    fail(
+        "dtg",
+        &["-X", "Xg6L0?M"],
+        2,
+        "Failed to parse an \"x\" format minute char at position 5: `Xg6L0?M`",
+    );
+}
+
+#[test]
+fn invalid_format() {
+    for (i, specifier, position) in [("%K", "%K", 0), ("%Y-%", "%", 3), ("%A %5", "%5", 3)] {
+        fail(
+            "dtg",
+            &["-f", i],
+            11,
+            &format!("Invalid format specifier `{specifier}` at position {position}: `{i}`"),
+        );
+    }
+    fail(
+        "dtg",
+        &["-n", "dtg", "-n", "x%Ez"],
+        11,
+        "Invalid format specifier `%E` at position 1: `x%Ez`",
+    );
+}
+
+#[test]
+fn invalid_x_alphabet() {
+    fail(
+        "dtg",
+        &["-x", "--x-alphabet", "0120"],
+        7,
+        "Invalid \"x\" format alphabet: duplicate `0`",
+    );
+    fail(
+        "dtg",
+        &["-n", "x:0120", SECONDS],
+        7,
+        "Invalid \"x\" format alphabet: duplicate `0`",
+    );
+}
+
+#[test]
+fn from_x_and_from_bcd() {
+    fail(
+        "dtg",
+        &["-X", "-B", X],
+        6,
+        "Options `-X` and `-B` are mutually exclusive",
+    );
+    fail(
+        "dtg",
+        &["-B", "-M", X],
+        6,
+        "Options `-B` and `-M` are mutually exclusive",
     );
 }
 
@@ -3,12 +3,17 @@
 //--------------------------------------------------------------------------------------------------
 // Crates
 
-use std::{collections::HashMap, sync::LazyLock};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{LazyLock, RwLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 pub use jiff::{
-    Span, Timestamp,
-    civil::{Date, Time},
-    tz::TimeZone,
+    SignedDuration, Span, Timestamp,
+    civil::{Date, DateTime, Time},
+    tz::{Offset, TimeZone, TimeZoneDatabase},
 };
 
 //--------------------------------------------------------------------------------------------------
@@ -37,48 +42,456 @@ static ITOC: LazyLock<HashMap<i8, char>> = LazyLock::new(||{
     .collect()
 });
 
-static CTOI: LazyLock<HashMap<char, i8>> =
-    LazyLock::new(|| ITOC.iter().map(|(i, c)| (*c, *i)).collect());
+/// "x" format fixed width fields and their maximum values
+const X_FIELDS: [(&str, u32); 5] = [
+    ("month", 11),
+    ("day", 30),
+    ("hour", 23),
+    ("minute", 59),
+    ("second", 59),
+];
+
+#[rustfmt::skip]
+const BCD_FIELDS: [(&str, u8, u8); 7] = [
+    ("year", 0, 99), ("year", 0, 99), ("month", 1, 12), ("day", 1, 31),
+    ("hour", 0, 23), ("minute", 0, 59), ("second", 0, 59),
+];
+
+/// Military time zone letters, names, and offsets in hours (`J` is local time, without an offset)
+#[rustfmt::skip]
+const MILITARY_ZONES: [(char, &str, i8); 25] = [
+    ('Z', "Zulu", 0),
+    ('A', "Alpha", 1), ('B', "Bravo", 2), ('C', "Charlie", 3), ('D', "Delta", 4),
+    ('E', "Echo", 5), ('F', "Foxtrot", 6), ('G', "Golf", 7), ('H', "Hotel", 8),
+    ('I', "India", 9), ('K', "Kilo", 10), ('L', "Lima", 11), ('M', "Mike", 12),
+    ('N', "November", -1), ('O', "Oscar", -2), ('P', "Papa", -3), ('Q', "Quebec", -4),
+    ('R', "Romeo", -5), ('S', "Sierra", -6), ('T', "Tango", -7), ('U', "Uniform", -8),
+    ('V', "Victor", -9), ('W', "Whiskey", -10), ('X', "X-ray", -11), ('Y', "Yankee", -12),
+];
+
+/// Timezone database, its version, and an invalid `DTG_TZDB` environment variable value, if any
+struct TzDb {
+    db: TimeZoneDatabase,
+    version: Option<String>,
+    invalid: Option<String>,
+}
+
+/// Timezone database in use (initially from the `DTG_TZDB` environment variable)
+static TZ_DB: LazyLock<RwLock<TzDb>> = LazyLock::new(|| {
+    let system = || tz_db_open("system").unwrap_or_else(|_| (TimeZoneDatabase::from_env(), None));
+    let source = std::env::var("DTG_TZDB").ok();
+    let ((db, version), invalid) = match source.as_deref().map(tz_db_open) {
+        Some(Ok(db)) => (db, None),
+        Some(Err(_)) => (system(), source),
+        None => (system(), None),
+    };
+    RwLock::new(TzDb {
+        db,
+        version,
+        invalid,
+    })
+});
+/// User-defined timezone aliases (lowercase) and their comma-separated timezones
+static TZ_ALIASES: LazyLock<RwLock<HashMap<String, String>>> = LazyLock::new(RwLock::default);
+
+/// Preferred zones for ambiguous timezone abbreviations
+const TZ_ABBREVIATION_PREFERENCES: [(&str, &str); 37] = [
+    ("ACDT", "Australia/Adelaide"),
+    ("ACST", "Australia/Adelaide"),
+    ("ADT", "America/Halifax"),
+    ("AEDT", "Australia/Sydney"),
+    ("AEST", "Australia/Sydney"),
+    ("AKDT", "America/Anchorage"),
+    ("AKST", "America/Anchorage"),
+    ("AST", "America/Halifax"),
+    ("AWST", "Australia/Perth"),
+    ("BST", "Europe/London"),
+    ("CAT", "Africa/Maputo"),
+    ("CDT", "America/Chicago"),
+    ("CEST", "Europe/Berlin"),
+    ("CET", "Europe/Berlin"),
+    ("CST", "America/Chicago"),
+    ("EAT", "Africa/Nairobi"),
+    ("EDT", "America/New_York"),
+    ("EEST", "Europe/Athens"),
+    ("EET", "Europe/Athens"),
+    ("EST", "America/New_York"),
+    ("GMT", "Europe/London"),
+    ("HKT", "Asia/Hong_Kong"),
+    ("HST", "Pacific/Honolulu"),
+    ("IST", "Asia/Kolkata"),
+    ("JST", "Asia/Tokyo"),
+    ("KST", "Asia/Seoul"),
+    ("MDT", "America/Denver"),
+    ("MSK", "Europe/Moscow"),
+    ("MST", "America/Denver"),
+    ("NZDT", "Pacific/Auckland"),
+    ("NZST", "Pacific/Auckland"),
+    ("PDT", "America/Los_Angeles"),
+    ("PST", "America/Los_Angeles"),
+    ("SAST", "Africa/Johannesburg"),
+    ("WAT", "Africa/Lagos"),
+    ("WEST", "Europe/Lisbon"),
+    ("WET", "Europe/Lisbon"),
+];
+
+/// Window of the timezone abbreviation index (2020-01-01 to 2030-01-01 UTC)
+const TZ_ABBREVIATION_WINDOW: (Timestamp, Timestamp) = (
+    Timestamp::constant(1_577_836_800, 0),
+    Timestamp::constant(1_893_456_000, 0),
+);
+
+/// Timezone abbreviations in use within [`TZ_ABBREVIATION_WINDOW`] and their candidate zones (built
+/// on first use and after [`tz_db_set`])
+static TZ_ABBREVIATIONS: RwLock<Option<HashMap<String, Vec<TzAbbreviation>>>> = RwLock::new(None);
+
+/// Countries and their timezones (tzdb `zone.tab`)
+const ZONE_TAB: &str = include_str!("zone.tab");
+
+#[rustfmt::skip]
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
 
 //--------------------------------------------------------------------------------------------------
 // DtgError struct
 
 /**
-Custom error
+Error kind with its structured payload and code (see [`DtgErrorKind::code`])
+
+Positions are char indexes into the input
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DtgErrorKind {
+    /// 101: Invalid timestamp
+    InvalidTimestamp { input: String },
+    /// 102: Invalid timezone
+    InvalidTimezone { input: String },
+    /// 102: Invalid `TZif` file
+    InvalidTzif { path: String },
+    /// 103: Failed to get local timezone
+    LocalTimezone,
+    /// 104: Failed to get elapsed time
+    Elapsed,
+    /// 105: Failed to parse an "x" format component char
+    InvalidXChar {
+        input: String,
+        component: &'static str,
+        position: usize,
+    },
+    /// 107: Failed to parse a "bcd" format component char
+    InvalidBcdChar {
+        input: String,
+        component: &'static str,
+        position: usize,
+    },
+    /// 108: Invalid "bcd" format component value
+    InvalidBcdValue {
+        component: &'static str,
+        value: u8,
+        min: u8,
+        max: u8,
+    },
+    /// 109: Invalid "x" format alphabet
+    InvalidXAlphabet { reason: String },
+    /// 110: Invalid duration
+    InvalidDuration { input: String },
+    /// 110: Failed to get duration
+    Duration,
+    /// 111: Invalid interval
+    InvalidInterval { input: String },
+    /// 112: Invalid military time zone letter
+    InvalidMilitaryZone { letter: char },
+    /// 113: Ambiguous timezone abbreviation
+    AmbiguousAbbreviation {
+        abbreviation: String,
+        candidates: Vec<String>,
+    },
+    /// 114: Invalid timezone database
+    InvalidTimezoneDatabase { database: String },
+    /// 115: Invalid timezone alias
+    InvalidTimezoneAlias { alias: String, zones: String },
+    /// 116: Invalid working hours
+    InvalidWorkingHours { input: String },
+    /// 117: Invalid named format
+    InvalidNamedFormat { name: String },
+    /// 117: Invalid format
+    InvalidFormat { input: String },
+    /// 118: Invalid system time
+    InvalidSystemTime { dtg: Dtg },
+    /// 119: Invalid format specifier
+    InvalidFormatSpecifier {
+        format: String,
+        specifier: String,
+        position: usize,
+    },
+    /// Any code: Error created via the deprecated [`DtgError::new`]
+    Other { code: usize, message: String },
+}
+
+impl DtgErrorKind {
+    /**
+    Get the numeric code
+
+    ```
+    use dtg_lib::{Dtg, DtgErrorKind};
+
+    let e = Dtg::from("nope").unwrap_err();
+
+    assert_eq!(e.code(), 101);
+    assert_eq!(e.kind().code(), 101);
+    assert_eq!(DtgErrorKind::LocalTimezone.code(), 103);
+    ```
+    */
+    #[must_use]
+    pub fn code(&self) -> usize {
+        match self {
+            DtgErrorKind::InvalidTimestamp { .. } => 101,
+            DtgErrorKind::InvalidTimezone { .. } | DtgErrorKind::InvalidTzif { .. } => 102,
+            DtgErrorKind::LocalTimezone => 103,
+            DtgErrorKind::Elapsed => 104,
+            DtgErrorKind::InvalidXChar { .. } => 105,
+            DtgErrorKind::InvalidBcdChar { .. } => 107,
+            DtgErrorKind::InvalidBcdValue { .. } => 108,
+            DtgErrorKind::InvalidXAlphabet { .. } => 109,
+            DtgErrorKind::InvalidDuration { .. } | DtgErrorKind::Duration => 110,
+            DtgErrorKind::InvalidInterval { .. } => 111,
+            DtgErrorKind::InvalidMilitaryZone { .. } => 112,
+            DtgErrorKind::AmbiguousAbbreviation { .. } => 113,
+            DtgErrorKind::InvalidTimezoneDatabase { .. } => 114,
+            DtgErrorKind::InvalidTimezoneAlias { .. } => 115,
+            DtgErrorKind::InvalidWorkingHours { .. } => 116,
+            DtgErrorKind::InvalidNamedFormat { .. } | DtgErrorKind::InvalidFormat { .. } => 117,
+            DtgErrorKind::InvalidSystemTime { .. } => 118,
+            DtgErrorKind::InvalidFormatSpecifier { .. } => 119,
+            DtgErrorKind::Other { code, .. } => *code,
+        }
+    }
+}
+
+impl std::fmt::Display for DtgErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DtgErrorKind::InvalidTimestamp { input } => write!(f, "Invalid timestamp: `{input}`"),
+            DtgErrorKind::InvalidTimezone { input } => write!(f, "Invalid timezone: `{input}`"),
+            DtgErrorKind::InvalidTzif { path } => write!(f, "Invalid TZif file: `{path}`"),
+            DtgErrorKind::LocalTimezone => write!(f, "Failed to get local timezone"),
+            DtgErrorKind::Elapsed => write!(f, "Failed to get elapsed time"),
+            DtgErrorKind::InvalidXChar {
+                input,
+                component,
+                position,
+            } => write!(
+                f,
+                "Failed to parse an \"x\" format {component} char at position {position}: \
+                `{input}`",
+            ),
+            DtgErrorKind::InvalidBcdChar {
+                input,
+                component,
+                position,
+            } => write!(
+                f,
+                "Failed to parse a \"bcd\" format {component} char at position {position}: \
+                `{input}`",
+            ),
+            DtgErrorKind::InvalidBcdValue {
+                component,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "Invalid \"bcd\" format {component}: {value} (expected {min}-{max})",
+            ),
+            DtgErrorKind::InvalidXAlphabet { reason } => {
+                write!(f, "Invalid \"x\" format alphabet: {reason}")
+            }
+            DtgErrorKind::InvalidDuration { input } => write!(f, "Invalid duration: `{input}`"),
+            DtgErrorKind::Duration => write!(f, "Failed to get duration"),
+            DtgErrorKind::InvalidInterval { input } => write!(f, "Invalid interval: `{input}`"),
+            DtgErrorKind::InvalidMilitaryZone { letter } => {
+                write!(f, "Invalid military time zone letter: `{letter}`")
+            }
+            DtgErrorKind::AmbiguousAbbreviation {
+                abbreviation,
+                candidates,
+            } => write!(
+                f,
+                "Ambiguous timezone abbreviation: `{abbreviation}` (candidates: {})",
+                candidates.join(", "),
+            ),
+            DtgErrorKind::InvalidTimezoneDatabase { database } => {
+                write!(f, "Invalid timezone database: `{database}`")
+            }
+            DtgErrorKind::InvalidTimezoneAlias { alias, zones } => {
+                write!(f, "Invalid timezone alias: `{alias}` = `{zones}`")
+            }
+            DtgErrorKind::InvalidWorkingHours { input } => {
+                write!(f, "Invalid working hours: `{input}`")
+            }
+            DtgErrorKind::InvalidNamedFormat { name } => {
+                write!(f, "Invalid named format: `{name}` is empty or built-in")
+            }
+            DtgErrorKind::InvalidFormat { input } => write!(f, "Invalid format: `{input}`"),
+            DtgErrorKind::InvalidSystemTime { dtg } => write!(f, "Invalid system time: `{dtg}`"),
+            DtgErrorKind::InvalidFormatSpecifier {
+                format,
+                specifier,
+                position,
+            } => write!(
+                f,
+                "Invalid format specifier `{specifier}` at position {position}: `{format}`",
+            ),
+            DtgErrorKind::Other { message, .. } => write!(f, "{message}"),
+        }
+    }
+}
+
+/**
+Custom error: a [`DtgErrorKind`] and the underlying [`jiff::Error`], if any, as its
+[source](std::error::Error::source)
 
 * 101: Invalid timestamp
 * 102: Invalid timezone
 * 103: Failed to get local timezone
 * 104: Failed to get elapsed time
 * 105: Failed to parse an "x" format component char
-* 106: Failed to convert usize to u32
+* 106: Unused (formerly failed to convert usize to u32)
+* 107: Failed to parse a "bcd" format component char
+* 108: Invalid "bcd" format component value
+* 109: Invalid "x" format alphabet
+* 110: Invalid duration
+* 111: Invalid interval
+* 112: Invalid military time zone letter
+* 113: Ambiguous timezone abbreviation
+* 114: Invalid timezone database
+* 115: Invalid timezone alias
+* 116: Invalid working hours
+* 117: Invalid named format
+* 118: Invalid system time
+* 119: Invalid format specifier
+
+```
+use {
+    dtg_lib::{Dtg, DtgErrorKind, TimeZone},
+    std::error::Error,
+};
+
+let e = Dtg::from_format("2022-02-30", "%Y-%m-%d", &TimeZone::UTC).unwrap_err();
+
+assert_eq!(e.kind(), &DtgErrorKind::InvalidTimestamp { input: "2022-02-30".into() });
+assert_eq!(e.to_string(), "Invalid timestamp: `2022-02-30`");
+assert!(e.source().is_some());
+```
 */
 #[derive(Debug)]
 pub struct DtgError {
+    /// Numeric code (copied on creation; changing it has no effect on [`DtgError::code`])
+    #[deprecated(note = "use `DtgError::code()` or `DtgError::kind()`")]
     pub code: usize,
+    /// Message (copied on creation; changing it has no effect on the
+    /// [Display](#impl-Display-for-DtgError) message)
+    #[deprecated(note = "use `DtgError::to_string()` or `DtgError::kind()`")]
     pub message: String,
+    kind: DtgErrorKind,
+    source: Option<jiff::Error>,
 }
 
 impl DtgError {
-    /// Create error
+    /**
+    Create error
+
+    ```
+    # #![allow(deprecated)]
+    use dtg_lib::{tz, DtgError};
+
+    let mut e = DtgError::new("Invalid timezone: `nope`", 102);
+
+    assert_eq!(e, tz("nope").unwrap_err());
+    assert_eq!(e.code, 102);
+    assert_eq!(e.message(), "Invalid timezone: `nope`");
+
+    e.code = 999;
+    assert_eq!(e.code(), 102);
+    ```
+    */
+    #[deprecated(note = "use `DtgErrorKind` and `DtgError::from`")]
     #[must_use]
     pub fn new(message: &str, code: usize) -> DtgError {
-        DtgError {
+        DtgError::from(DtgErrorKind::Other {
             code,
             message: message.to_string(),
+        })
+    }
+
+    /**
+    Get the message
+    */
+    #[deprecated(note = "use `DtgError::to_string()`")]
+    #[must_use]
+    pub fn message(&self) -> String {
+        self.kind.to_string()
+    }
+
+    /**
+    Get the kind
+    */
+    #[must_use]
+    pub fn kind(&self) -> &DtgErrorKind {
+        &self.kind
+    }
+
+    /**
+    Get the numeric code (see [`DtgErrorKind::code`])
+    */
+    #[must_use]
+    pub fn code(&self) -> usize {
+        self.kind.code()
+    }
+
+    /**
+    Attach the underlying [`jiff::Error`]
+    */
+    fn with_source(mut self, source: jiff::Error) -> DtgError {
+        self.source = Some(source);
+        self
+    }
+}
+
+impl From<DtgErrorKind> for DtgError {
+    #[allow(deprecated)]
+    fn from(kind: DtgErrorKind) -> DtgError {
+        DtgError {
+            code: kind.code(),
+            message: kind.to_string(),
+            kind,
+            source: None,
         }
     }
 }
 
 impl std::fmt::Display for DtgError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", self.message)
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl std::error::Error for DtgError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|e| e as &(dyn std::error::Error + 'static))
     }
 }
 
 impl std::cmp::PartialEq for DtgError {
+    /**
+    Errors are equal if their codes and messages are equal (sources are ignored)
+    */
     fn eq(&self, other: &DtgError) -> bool {
-        self.code == other.code && self.message == other.message
+        self.code() == other.code() && self.kind.to_string() == other.kind.to_string()
     }
 }
 
@@ -88,7 +501,7 @@ impl std::cmp::PartialEq for DtgError {
 /**
 Date time group
 */
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Dtg {
     dt: Timestamp,
 }
@@ -144,7 +557,10 @@ impl Dtg {
                 return Ok(Dtg { dt });
             }
         }
-        Err(DtgError::new(&format!("Invalid timestamp: `{s}`"), 101))
+        Err(DtgErrorKind::InvalidTimestamp {
+            input: s.to_string(),
+        }
+        .into())
     }
 
     /**
@@ -179,78 +595,418 @@ impl Dtg {
             return Ok(Dtg { dt });
         }
 
-        Err(DtgError::new(
-            &format!(
-                "Invalid timestamp: `{year}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z`",
-            ),
-            101,
-        ))
+        Err(DtgErrorKind::InvalidTimestamp {
+            input: format!("{year}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z"),
+        }
+        .into())
     }
 
     /**
-    Create a [Dtg] from an "x" format timestamp
+    Create a [Dtg] from an "x" or extended "x" format timestamp
 
     ```
-    use dtg_lib::{Dtg, Format};
+    use dtg_lib::{tz, Dtg, Format, Timestamp};
 
     let dtg = Dtg::from_x("Xg6L02M").unwrap();
 
     assert_eq!(dtg.format(&Some(Format::custom("%s")), &None), "1658448142");
     assert_eq!(dtg.rfc_3339(), "2022-07-22T00:02:22Z");
+
+    // Fractional seconds and offset
+    let dtg = Dtg::from_x("Xg6KI2M.U-60").unwrap();
+
+    assert_eq!(dtg, Dtg::from_dt(&Timestamp::new(1658448142, 500_000_000).unwrap()));
+    assert_eq!(dtg.x_extended_format(&tz("MST7MDT").ok()), "Xg6KI2M.U-60");
+
+    // Negative years
+    let dtg = Dtg::from_x("-i2E000").unwrap();
+
+    assert_eq!(dtg, Dtg::from_ymd_hms(-44, 3, 15, 0, 0, 0).unwrap());
+    assert_eq!(dtg.x_format(), "-i2E000");
     ```
 
     # Errors
 
-    Returns an error if not able to parse the given `&str` as an "x" format timestamp
+    Returns an error if not able to parse the given `&str` as an "x" or extended "x" format
+    timestamp
     */
     pub fn from_x(s: &str) -> Result<Dtg, DtgError> {
-        fn next(chars: &mut std::iter::Rev<std::str::Chars>) -> Option<i8> {
-            if let Some(x) = chars.next() {
-                CTOI.get(&x).copied()
-            } else {
-                None
-            }
+        Dtg::from_x_with(s, &XAlphabet::default())
+    }
+
+    /**
+    Create a [Dtg] from an "x" or extended "x" format timestamp with an alphabet
+
+    ```
+    use dtg_lib::{Dtg, XAlphabet};
+
+    let dtg = Dtg::from_x_with("1k66l0020m", &XAlphabet::dns()).unwrap();
+
+    assert_eq!(dtg.rfc_3339(), "2022-07-22T00:02:22Z");
+    assert_eq!(Dtg::from_x_with("1K66L0020M", &XAlphabet::dns()).unwrap(), dtg);
+
+    let alphabet = XAlphabet::new("0123456789").unwrap();
+    let dtg = Dtg::from_x_with("20220621000222", &alphabet).unwrap();
+
+    assert_eq!(dtg.rfc_3339(), "2022-07-22T00:02:22Z");
+    ```
+
+    # Errors
+
+    Returns an error if not able to parse the given `&str` as an "x" or extended "x" format
+    timestamp with the given alphabet
+    */
+    pub fn from_x_with(s: &str, alphabet: &XAlphabet) -> Result<Dtg, DtgError> {
+        let invalid = || {
+            DtgError::from(DtgErrorKind::InvalidTimestamp {
+                input: s.to_string(),
+            })
+        };
+        let invalid_char = |component, position| {
+            DtgError::from(DtgErrorKind::InvalidXChar {
+                input: s.to_string(),
+                component,
+                position,
+            })
+        };
+
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let (rest, offset) = match rest.find(['+', '-']) {
+            Some(i) => (&rest[..i], Some(&rest[i..])),
+            None => (rest, None),
+        };
+        let (rest, fraction) = match rest.split_once('.') {
+            Some((rest, fraction)) => (rest, Some(fraction)),
+            None => (rest, None),
+        };
+
+        let chars = rest.chars().collect::<Vec<char>>();
+        let sign = usize::from(negative);
+        let mut end = chars.len();
+        let mut fields = [0; 5];
+        for (i, (component, max)) in X_FIELDS.iter().enumerate().rev() {
+            let start = end.checked_sub(alphabet.width(*max)).ok_or_else(invalid)?;
+            let Some(field) = alphabet
+                .decode(&chars[start..end])
+                .and_then(|n| i8::try_from(n).ok())
+            else {
+                return Err(invalid_char(component, sign + start));
+            };
+            end = start;
+            fields[i] = field;
         }
+        let [month, day, hour, minute, second] = fields;
 
-        let mut chars = s.chars().rev();
-
-        let mut second = 0;
-        let mut minute = 0;
-        let mut hour = 0;
-        let mut day = 0;
-        let mut month = 0;
-        for (x, component, offset) in &mut [
-            (&mut second, "second", 0),
-            (&mut minute, "minute", 0),
-            (&mut hour, "hour", 0),
-            (&mut day, "day", 1),
-            (&mut month, "month", 1),
-        ] {
-            if let Some(c) = next(&mut chars) {
-                **x = c + *offset;
-            } else {
-                return Err(DtgError::new(
-                    &format!("Failed to parse an \"x\" format {component} char"),
-                    105,
-                ));
+        let year = if end == 0 {
+            0
+        } else if end > alphabet.width(9999) {
+            return Err(invalid());
+        } else {
+            alphabet
+                .decode(&chars[..end])
+                .and_then(|n| i16::try_from(n).ok())
+                .ok_or_else(invalid)?
+        };
+        let year = match (negative, year) {
+            (false, year) => year,
+            (true, 0) => return Err(invalid()),
+            (true, year) => -year,
+        };
+
+        let fraction_start = sign + chars.len() + 1;
+        let nanosecond = match fraction {
+            Some(fraction) => x_fraction(fraction, alphabet)
+                .ok_or_else(invalid)?
+                .map_err(|i| invalid_char("fraction", fraction_start + i))?,
+            None => 0,
+        };
+        let offset_start = sign + chars.len() + fraction.map_or(0, |x| x.chars().count() + 1);
+        let offset_seconds = match offset {
+            Some(offset) => x_offset(offset, alphabet)
+                .ok_or_else(invalid)?
+                .map_err(|i| invalid_char("offset", offset_start + i))?,
+            None => 0,
+        };
+
+        Offset::from_seconds(offset_seconds)
+            .and_then(|offset| {
+                Date::new(year, month + 1, day + 1)?
+                    .to_datetime(Time::new(hour, minute, second, nanosecond)?)
+                    .to_zoned(TimeZone::fixed(offset))
+            })
+            .map(|zdt| Dtg {
+                dt: zdt.timestamp(),
+            })
+            .map_err(|e| invalid().with_source(e))
+    }
+
+    /**
+    Create a start and end [Dtg] from an "x" format interval: `START/END` or `START/+DURATION`
+
+    `START` and `END` are "x" or extended "x" format timestamps and `DURATION` is an "x" format
+    [Duration].
+
+    ```
+    use dtg_lib::Dtg;
+
+    let (start, end) = Dtg::from_x_interval("Xg6L02M/Xg6L1WM").unwrap();
+
+    assert_eq!(start.rfc_3339(), "2022-07-22T00:02:22Z");
+    assert_eq!(end.rfc_3339(), "2022-07-22T01:32:22Z");
+    assert_eq!(Dtg::from_x_interval("Xg6L02M/+1U0").unwrap(), (start, end));
+    ```
+
+    # Errors
+
+    Returns an error if not able to parse the given `&str` as an "x" format interval
+    */
+    pub fn from_x_interval(s: &str) -> Result<(Dtg, Dtg), DtgError> {
+        Dtg::from_x_interval_with(s, &XAlphabet::default())
+    }
+
+    /**
+    Create a start and end [Dtg] from an "x" format interval with an alphabet
+
+    # Errors
+
+    Returns an error if not able to parse the given `&str` as an "x" format interval with the given
+    alphabet
+    */
+    pub fn from_x_interval_with(s: &str, alphabet: &XAlphabet) -> Result<(Dtg, Dtg), DtgError> {
+        let invalid = || {
+            DtgError::from(DtgErrorKind::InvalidInterval {
+                input: s.to_string(),
+            })
+        };
+        let Some((start, end)) = s.split_once('/') else {
+            return Err(invalid());
+        };
+        let start = Dtg::from_x_with(start, alphabet)?;
+        let end = match end.strip_prefix('+') {
+            Some(duration) => {
+                let duration = Duration::from_x_with(duration, alphabet)?;
+                match duration
+                    .signed_duration()
+                    .and_then(|d| start.dt.checked_add(d).ok())
+                {
+                    Some(dt) => Dtg { dt },
+                    None => return Err(invalid()),
+                }
             }
+            None => Dtg::from_x_with(end, alphabet)?,
+        };
+        Ok((start, end))
+    }
+
+    /**
+    Create a [Dtg] from a "bcd" format timestamp in the given timezone
+
+    ```
+    use dtg_lib::{tz, Dtg, TimeZone};
+
+    let dtg = Dtg::from_bcd("⠄⠤|⢰|⠤|⠀|⠠|⠤", &TimeZone::UTC).unwrap();
+
+    assert_eq!(dtg.rfc_3339(), "2022-07-22T00:02:22Z");
+
+    let mt = tz("MST7MDT").unwrap();
+    let dtg = Dtg::from_bcd("⠄⠤|⢰|⢄|⡈|⠠|⠤", &mt).unwrap();
+
+    assert_eq!(dtg.rfc_3339(), "2022-07-22T00:02:22Z");
+
+    // Negative years use a `-` prefix
+    let dtg = Dtg::from_bcd("-⠀⠒|⢠|⣐|⠀|⠀|⠀", &TimeZone::UTC).unwrap();
+
+    assert_eq!(dtg, Dtg::from_ymd_hms(-44, 3, 15, 0, 0, 0).unwrap());
+
+    // Each field is range checked
+    assert_eq!(
+        Dtg::from_bcd("⠄⠤|⣠|⠤|⠀|⠠|⠤", &TimeZone::UTC).unwrap_err().to_string(),
+        "Invalid \"bcd\" format month: 13 (expected 1-12)",
+    );
+    ```
+
+    # Errors
+
+    Returns an error if not able to parse the given `&str` as a "bcd" format timestamp, including
+    when a field is outside its range (month: 1-12, day: 1-31, hour: 0-23, minute and second: 0-59)
+    */
+    pub fn from_bcd(s: &str, tz: &TimeZone) -> Result<Dtg, DtgError> {
+        let invalid = || {
+            DtgError::from(DtgErrorKind::InvalidTimestamp {
+                input: s.to_string(),
+            })
+        };
+        let (negative, t) = match s.strip_prefix('-') {
+            Some(t) => (true, t),
+            None => (false, s),
+        };
+
+        let chars = t.split('|').collect::<Vec<&str>>();
+        if chars.len() != 6
+            || chars[0].chars().count() != 2
+            || chars[1..].iter().any(|x| x.chars().count() != 1)
+        {
+            return Err(invalid());
         }
 
-        let mut year = 0;
-        for (exp, c) in chars.enumerate() {
-            if let Some(i) = CTOI.get(&c) {
-                if let Ok(j) = u32::try_from(exp) {
-                    year += i16::from(*i) * 60_i16.pow(j);
-                } else {
-                    return Err(DtgError::new("Failed to convert usize to u32", 106));
+        // Char position of field `i`: the sign, 2 chars, then 1 char after each `|`
+        let sign = usize::from(negative);
+        let mut fields = [0; 7];
+        for (i, (c, (component, min, max))) in chars.concat().chars().zip(BCD_FIELDS).enumerate() {
+            if !('\u{2800}'..='\u{28ff}').contains(&c)
+                || bbd_lib::encode_bcd(bbd_lib::decode_bcd(c)) != c
+            {
+                return Err(DtgErrorKind::InvalidBcdChar {
+                    input: s.to_string(),
+                    component,
+                    position: sign + i + i.saturating_sub(1),
                 }
-            } else {
-                return Err(DtgError::new(&format!("Invalid timestamp: `{s}`"), 101));
+                .into());
+            }
+            let n = bbd_lib::decode_bcd(c);
+            if n < min || n > max {
+                return Err(DtgErrorKind::InvalidBcdValue {
+                    component,
+                    value: n,
+                    min,
+                    max,
+                }
+                .into());
             }
+            fields[i] = n;
         }
+        let [cc, yy, month, day, hour, minute, second] = fields;
 
-        Dtg::from_ymd_hms(year, month, day, hour, minute, second)
-            .map_err(|e| DtgError::new(&format!("Invalid timestamp: `{s}`: {e}"), 101))
+        let year = i16::from(cc) * 100 + i16::from(yy);
+        let year = if negative { -year } else { year };
+        #[allow(clippy::cast_possible_wrap)]
+        Date::new(year, month as i8, day as i8)
+            .and_then(|d| Ok(d.to_datetime(Time::new(hour as i8, minute as i8, second as i8, 0)?)))
+            .and_then(|dt| dt.to_zoned(tz.clone()))
+            .map(|zdt| Dtg {
+                dt: zdt.timestamp(),
+            })
+            .map_err(|e| invalid().with_source(e))
+    }
+
+    /**
+    Create a [Dtg] from a military date time group (`DDHHMMZ MON YY`)
+
+    ```
+    use dtg_lib::{Dtg, DtgErrorKind};
+
+    let dtg = Dtg::from_military("220002Z JUL 22").unwrap();
+
+    assert_eq!(dtg.rfc_3339(), "2022-07-22T00:02:00Z");
+
+    // The zone letter gives the offset; spaces and case are optional
+    assert_eq!(Dtg::from_military("211802s jul 22").unwrap(), dtg);
+    assert_eq!(Dtg::from_military("220502EJUL22").unwrap(), dtg);
+
+    // `J` (local time) has no offset
+    assert_eq!(
+        Dtg::from_military("220002J JUL 22").unwrap_err().kind(),
+        &DtgErrorKind::InvalidMilitaryZone { letter: 'J' },
+    );
+    ```
+
+    Two digit years are in 1969-2068, like `%y`.
+
+    # Errors
+
+    Returns an error if not able to parse the given `&str` as a military date time group, including
+    when the zone letter is not one of the 25 military time zones with an offset (`J` is local time)
+    */
+    pub fn from_military(s: &str) -> Result<Dtg, DtgError> {
+        let invalid = || {
+            DtgError::from(DtgErrorKind::InvalidTimestamp {
+                input: s.to_string(),
+            })
+        };
+
+        let t = s
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_ascii_uppercase())
+            .collect::<Vec<char>>();
+        if t.len() != 12 {
+            return Err(invalid());
+        }
+        let number = |range: std::ops::Range<usize>| {
+            t[range]
+                .iter()
+                .try_fold(0_i8, |n, c| {
+                    Some(n * 10 + i8::try_from(c.to_digit(10)?).ok()?)
+                })
+                .ok_or_else(invalid)
+        };
+        let (day, hour, minute, yy) =
+            (number(0..2)?, number(2..4)?, number(4..6)?, number(10..12)?);
+        let Some((_, _, offset)) = MILITARY_ZONES.iter().find(|(c, _, _)| *c == t[6]) else {
+            return Err(DtgErrorKind::InvalidMilitaryZone { letter: t[6] }.into());
+        };
+        let month = t[7..10].iter().collect::<String>();
+        let month = MONTHS
+            .iter()
+            .position(|x| *x == month)
+            .and_then(|i| i8::try_from(i + 1).ok())
+            .ok_or_else(invalid)?;
+        let year = i16::from(yy) + if yy < 69 { 2000 } else { 1900 };
+
+        Date::new(year, month, day)
+            .and_then(|d| Ok(d.to_datetime(Time::new(hour, minute, 0, 0)?)))
+            .and_then(|dt| dt.to_zoned(TimeZone::fixed(Offset::from_hours(*offset)?)))
+            .map(|zdt| Dtg {
+                dt: zdt.timestamp(),
+            })
+            .map_err(|_| invalid())
+    }
+
+    /**
+    Create a [Dtg] from a custom format (see [Format]) in a timezone, unless it has an offset or
+    is an epoch time (`%s`)
+
+    ```
+    use dtg_lib::{tz, Dtg};
+
+    let est = tz("EST5EDT").unwrap();
+    let dtg = Dtg::from_format("2022-07-21 20:02:22", "%Y-%m-%d %H:%M:%S", &est).unwrap();
+
+    assert_eq!(dtg.rfc_3339(), "2022-07-22T00:02:22Z");
+    assert_eq!(Dtg::from_format("21/07/2022 20:02:22 -0400", "%d/%m/%Y %T %z", &est).unwrap(), dtg);
+    assert_eq!(Dtg::from_format("1658448142", "%s", &est).unwrap(), dtg);
+    assert_eq!(
+        Dtg::from_format("2022-07-21", "%F", &est).unwrap().rfc_3339(),
+        "2022-07-21T04:00:00Z",
+    );
+    assert!(Dtg::from_format("20:02:22", "%T", &est).is_err());
+    ```
+
+    # Errors
+
+    Returns an error if not able to parse the given `&str` with the format, or if it doesn't have
+    at least a year, month, and day
+    */
+    pub fn from_format(s: &str, format: &str, tz: &TimeZone) -> Result<Dtg, DtgError> {
+        let invalid = |e| {
+            DtgError::from(DtgErrorKind::InvalidTimestamp {
+                input: s.to_string(),
+            })
+            .with_source(e)
+        };
+        let tm = jiff::fmt::strtime::parse(format, s).map_err(invalid)?;
+        let dt = match tm.to_timestamp() {
+            Ok(dt) => dt,
+            Err(_) => tm
+                .to_datetime()
+                .and_then(|x| x.to_zoned(tz.clone()))
+                .map_err(invalid)?
+                .timestamp(),
+        };
+        Ok(Dtg { dt })
     }
 
     /**
@@ -271,6 +1027,22 @@ impl Dtg {
         Dtg { dt: *dt }
     }
 
+    /**
+    Get the [Timestamp]
+
+    ```
+    use dtg_lib::{Dtg, Timestamp};
+
+    let dtg = Dtg::from("1658448142").unwrap();
+
+    assert_eq!(dtg.timestamp(), Timestamp::new(1658448142, 0).unwrap());
+    ```
+    */
+    #[must_use]
+    pub fn timestamp(&self) -> Timestamp {
+        self.dt
+    }
+
     /**
     Format as a string
 
@@ -324,20 +1096,37 @@ impl Dtg {
     }
 
     /**
-    Format as "a" format
+    Format as extended "x" format
 
     ```
     use dtg_lib::{tz, Dtg};
 
-    let dtg = Dtg::from("1658448142").unwrap();
-    let a_utc = "\
-    1658448142.000000000
-    2022-07-22T00:02:22Z
-    Fri 22 Jul 2022 00:02:22 UTC
-    Fri 22 Jul 2022 00:02:22 UTC";
-    let a_mt = "\
-    1658448142.000000000
-    2022-07-22T00:02:22Z
+    let dtg = Dtg::from("1658448142.5").unwrap();
+
+    assert_eq!(dtg.x_extended_format(&None), "Xg6L02M.U");
+    assert_eq!(dtg.x_extended_format(&tz("Asia/Kolkata").ok()), "Xg6L5WM.U+5U");
+    ```
+    */
+    #[must_use]
+    pub fn x_extended_format(&self, tz: &Option<TimeZone>) -> String {
+        self.format(&Some(Format::XExtended), tz)
+    }
+
+    /**
+    Format as "a" format
+
+    ```
+    use dtg_lib::{tz, Dtg};
+
+    let dtg = Dtg::from("1658448142").unwrap();
+    let a_utc = "\
+    1658448142.000000000
+    2022-07-22T00:02:22Z
+    Fri 22 Jul 2022 00:02:22 UTC
+    Fri 22 Jul 2022 00:02:22 UTC";
+    let a_mt = "\
+    1658448142.000000000
+    2022-07-22T00:02:22Z
     Fri 22 Jul 2022 00:02:22 UTC
     Thu 21 Jul 2022 18:02:22 MDT";
 
@@ -367,6 +1156,23 @@ impl Dtg {
         self.format(&Some(Format::BCD), &None)
     }
 
+    /**
+    Format as a military date time group
+
+    ```
+    use dtg_lib::{tz, Dtg};
+
+    let dtg = Dtg::from("1658448142").unwrap();
+
+    assert_eq!(dtg.military_format(&None), "220002Z JUL 22");
+    assert_eq!(dtg.military_format(&tz("MST7MDT").ok()), "211802S JUL 22");
+    ```
+    */
+    #[must_use]
+    pub fn military_format(&self, tz: &Option<TimeZone>) -> String {
+        self.format(&Some(Format::Military), tz)
+    }
+
     /**
     Format as a string with format and timezone
 
@@ -395,13 +1201,62 @@ impl Dtg {
         "Thursday",
     );
     ```
+
+    # Panics
+
+    Panics if a custom format fails to format (see [`Dtg::try_format`])
     */
     #[must_use]
     pub fn format(&self, fmt: &Option<Format>, tz: &Option<TimeZone>) -> String {
+        self.try_format(fmt, tz).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /**
+    Format as a string
+
+    Custom formats are not checked first; use [`Format::compile`] or [`Format::validate`] once to
+    report an invalid specifier with its position.
+
+    ```
+    use dtg_lib::{Dtg, DtgErrorKind, Format};
+
+    let dtg = Dtg::from("1658448142").unwrap();
+
+    assert_eq!(dtg.try_format(&Some(Format::custom("%A")), &None).unwrap(), "Friday");
+    assert_eq!(
+        dtg.try_format(&Some(Format::custom("%A %")), &None).unwrap_err().kind(),
+        &DtgErrorKind::InvalidFormat { input: "%A %".into() },
+    );
+    assert_eq!(Format::compile("%A %").unwrap_err().code(), 119);
+    ```
+
+    # Errors
+
+    Returns an error if a custom format fails to format
+    */
+    pub fn try_format(
+        &self,
+        fmt: &Option<Format>,
+        tz: &Option<TimeZone>,
+    ) -> Result<String, DtgError> {
         let tz = tz.clone().unwrap_or(TimeZone::UTC);
         match fmt {
             Some(fmt) => fmt.with(&self.dt, &tz),
-            None => Format::Custom(RFC_3339.to_string()).with(&self.dt, &tz),
+            None => Format::rfc_3339().with(&self.dt, &tz),
+        }
+    }
+
+    /**
+    Get the [Duration] from this [Dtg] until another
+
+    # Errors
+
+    Returns an error if it failed to get the duration
+    */
+    pub fn until(&self, other: &Dtg) -> Result<Duration, DtgError> {
+        match self.dt.until(other.dt) {
+            Ok(d) => Ok(Duration::new(d)),
+            Err(e) => Err(DtgError::from(DtgErrorKind::Duration).with_source(e)),
         }
     }
 
@@ -413,20 +1268,147 @@ impl Dtg {
     pub fn elapsed(&self) -> Result<Duration, DtgError> {
         match self.dt.until(Timestamp::now()) {
             Ok(d) => Ok(Duration::new(d)),
-            Err(_) => Err(DtgError::new("Failed to get elapsed time", 104)),
+            Err(e) => Err(DtgError::from(DtgErrorKind::Elapsed).with_source(e)),
+        }
+    }
+}
+
+impl std::fmt::Display for Dtg {
+    /**
+    Format as an RFC 3339 string with fractional seconds (if any) in UTC
+
+    ```
+    use dtg_lib::Dtg;
+
+    assert_eq!(Dtg::from("1658448142").unwrap().to_string(), "2022-07-22T00:02:22Z");
+    assert_eq!(Dtg::from("1658448142.5").unwrap().to_string(), "2022-07-22T00:02:22.5Z");
+    ```
+    */
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.dt)
+    }
+}
+
+impl std::str::FromStr for Dtg {
+    type Err = DtgError;
+
+    /**
+    Parse an RFC 3339 string (any offset) or an integer or float timestamp (see [`Dtg::from`])
+
+    ```
+    use dtg_lib::Dtg;
+
+    let dtg = Dtg::from("1658448142.5").unwrap();
+
+    assert_eq!(dtg.to_string().parse::<Dtg>().unwrap(), dtg);
+    assert_eq!("2022-07-21T18:02:22.5-06:00".parse::<Dtg>().unwrap(), dtg);
+    assert_eq!("1658448142.5".parse::<Dtg>().unwrap(), dtg);
+    assert_eq!("nope".parse::<Dtg>().unwrap_err().code(), 101);
+    ```
+    */
+    fn from_str(s: &str) -> Result<Dtg, DtgError> {
+        match s.parse::<Timestamp>() {
+            Ok(dt) => Ok(Dtg { dt }),
+            Err(_) => Dtg::from(s),
         }
     }
 }
 
-impl std::cmp::PartialEq for Dtg {
-    fn eq(&self, other: &Dtg) -> bool {
-        self.dt == other.dt
+impl From<Timestamp> for Dtg {
+    /**
+    Create a [Dtg] from a [Timestamp]
+
+    Use `into()` since `Dtg::from` is [`Dtg::from`] (parse a string timestamp)
+
+    ```
+    use dtg_lib::{Dtg, Timestamp};
+
+    let dt = Timestamp::new(1658448142, 0).unwrap();
+
+    assert_eq!(Dtg::from_dt(&dt), dt.into());
+    assert_eq!(Timestamp::from(Dtg::from_dt(&dt)), dt);
+    ```
+    */
+    fn from(dt: Timestamp) -> Dtg {
+        Dtg { dt }
+    }
+}
+
+impl From<Dtg> for Timestamp {
+    fn from(dtg: Dtg) -> Timestamp {
+        dtg.dt
+    }
+}
+
+impl From<SystemTime> for Dtg {
+    /**
+    Create a [Dtg] from a [`SystemTime`], saturating at [`Timestamp::MIN`] / [`Timestamp::MAX`]
+
+    ```
+    use {dtg_lib::Dtg, std::time::{Duration, SystemTime, UNIX_EPOCH}};
+
+    let t = UNIX_EPOCH + Duration::new(1658448142, 500_000_000);
+    let dtg = Dtg::from("1658448142.5").unwrap();
+
+    assert_eq!(dtg, t.into());
+    assert_eq!(SystemTime::try_from(dtg).unwrap(), t);
+    ```
+    */
+    fn from(t: SystemTime) -> Dtg {
+        let dt = Timestamp::try_from(t).unwrap_or(if t < UNIX_EPOCH {
+            Timestamp::MIN
+        } else {
+            Timestamp::MAX
+        });
+        Dtg { dt }
+    }
+}
+
+impl TryFrom<Dtg> for SystemTime {
+    type Error = DtgError;
+
+    /**
+    Convert a [Dtg] to a [`SystemTime`]
+
+    # Errors
+
+    Returns an error if the [Dtg] is out of the platform's [`SystemTime`] range
+    */
+    fn try_from(dtg: Dtg) -> Result<SystemTime, DtgError> {
+        let d = dtg.dt.as_duration();
+        if d.is_negative() {
+            UNIX_EPOCH.checked_sub(d.unsigned_abs())
+        } else {
+            UNIX_EPOCH.checked_add(d.unsigned_abs())
+        }
+        .ok_or_else(|| DtgErrorKind::InvalidSystemTime { dtg }.into())
     }
 }
 
 //--------------------------------------------------------------------------------------------------
 // Duration
 
+/**
+Duration
+
+# "x" format
+
+Sign (if negative), base 60 seconds, and fractional seconds like the extended "x" format; the last
+three digits of the seconds are hours (mod 60), minutes, and seconds
+
+```text
+[-]SECONDS[.FRACTION]
+```
+
+Duration | Seconds             | Result
+---------|---------------------|-------
+1h30m    | 5400 => 1*3600+30*60 | 1U0
+1.5s     | 1, 30/60            | 1.U
+-2m      | -120 => -(2*60)     | -20
+
+See also [`Duration::from_x`]
+*/
+#[derive(Clone, Debug)]
 pub struct Duration {
     d: Span,
 }
@@ -435,6 +1417,161 @@ impl Duration {
     fn new(d: Span) -> Duration {
         Duration { d }
     }
+
+    /**
+    Create a [Duration] from an "x" format duration
+
+    ```
+    use dtg_lib::Duration;
+
+    assert_eq!(Duration::from_x("1U0").unwrap().to_string(), "5400s");
+    assert_eq!(Duration::from_x("-20").unwrap().to_string(), "-120s");
+    assert_eq!(Duration::from_x("1.U").unwrap().to_x(), "1.U");
+    ```
+
+    # Errors
+
+    Returns an error if not able to parse the given `&str` as an "x" format duration
+    */
+    pub fn from_x(s: &str) -> Result<Duration, DtgError> {
+        Duration::from_x_with(s, &XAlphabet::default())
+    }
+
+    /**
+    Create a [Duration] from an "x" format duration with an alphabet
+
+    ```
+    use dtg_lib::{Duration, XAlphabet};
+
+    let duration = Duration::from_x_with("5400", &XAlphabet::new("0123456789").unwrap()).unwrap();
+
+    assert_eq!(duration.to_x(), "1U0");
+    ```
+
+    # Errors
+
+    Returns an error if not able to parse the given `&str` as an "x" format duration with the
+    given alphabet
+    */
+    pub fn from_x_with(s: &str, alphabet: &XAlphabet) -> Result<Duration, DtgError> {
+        let invalid = || {
+            DtgError::from(DtgErrorKind::InvalidDuration {
+                input: s.to_string(),
+            })
+        };
+
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let (rest, fraction) = match rest.split_once('.') {
+            Some((rest, fraction)) => (rest, Some(fraction)),
+            None => (rest, None),
+        };
+
+        let chars = rest.chars().collect::<Vec<char>>();
+        if chars.is_empty() || (chars.len() > 1 && alphabet.decode(&chars[..1]) == Some(0)) {
+            return Err(invalid());
+        }
+        let seconds = alphabet
+            .decode(&chars)
+            .and_then(|n| i64::try_from(n).ok())
+            .ok_or_else(invalid)?;
+        let nanoseconds = match fraction {
+            Some(fraction) => x_fraction(fraction, alphabet)
+                .ok_or_else(invalid)?
+                .map_err(|i| {
+                    DtgError::from(DtgErrorKind::InvalidXChar {
+                        input: s.to_string(),
+                        component: "fraction",
+                        position: usize::from(negative) + chars.len() + 1 + i,
+                    })
+                })?,
+            None => 0,
+        };
+        if negative && seconds == 0 && nanoseconds == 0 {
+            return Err(invalid());
+        }
+
+        let d = SignedDuration::new(seconds, nanoseconds);
+        Span::try_from(if negative { -d } else { d })
+            .map(Duration::new)
+            .map_err(|_| invalid())
+    }
+
+    /**
+    Format as "x" format
+
+    ```
+    use dtg_lib::Dtg;
+
+    let start = Dtg::from("1658448142").unwrap();
+    let end = Dtg::from("1658453542.5").unwrap();
+
+    assert_eq!(start.until(&end).unwrap().to_x(), "1U0.U");
+    assert_eq!(end.until(&start).unwrap().to_x(), "-1U0.U");
+    ```
+    */
+    #[must_use]
+    pub fn to_x(&self) -> String {
+        self.to_x_with(&XAlphabet::default())
+    }
+
+    /**
+    Format as "x" format with an alphabet
+    */
+    #[must_use]
+    pub fn to_x_with(&self, alphabet: &XAlphabet) -> String {
+        let nanoseconds = self.nanoseconds();
+        let mut r = if nanoseconds < 0 {
+            String::from("-")
+        } else {
+            String::new()
+        };
+        let nanoseconds = nanoseconds.unsigned_abs();
+        r.push_str(&alphabet.encode_u128(nanoseconds / 1_000_000_000, 1));
+        #[allow(clippy::cast_possible_truncation)]
+        let nanosecond = (nanoseconds % 1_000_000_000) as u32;
+        if nanosecond != 0 {
+            r.push('.');
+            r.push_str(
+                alphabet
+                    .encode_u128(
+                        x_fraction_units(nanosecond, alphabet),
+                        alphabet.fraction_digits(),
+                    )
+                    .trim_end_matches(alphabet.digits[0]),
+            );
+        }
+        r
+    }
+
+    /**
+    Get the total nanoseconds (days are 24 hours)
+    */
+    fn nanoseconds(&self) -> i128 {
+        let d = &self.d;
+        let seconds = ((i128::from(d.get_weeks()) * 7 + i128::from(d.get_days())) * 24
+            + i128::from(d.get_hours()))
+            * 3600
+            + i128::from(d.get_minutes()) * 60
+            + i128::from(d.get_seconds());
+        seconds * 1_000_000_000
+            + i128::from(d.get_milliseconds()) * 1_000_000
+            + i128::from(d.get_microseconds()) * 1_000
+            + i128::from(d.get_nanoseconds())
+    }
+
+    /**
+    Convert to a [`SignedDuration`]
+    */
+    fn signed_duration(&self) -> Option<SignedDuration> {
+        let nanoseconds = self.nanoseconds();
+        Some(SignedDuration::new(
+            i64::try_from(nanoseconds / 1_000_000_000).ok()?,
+            i32::try_from(nanoseconds % 1_000_000_000).ok()?,
+        ))
+    }
 }
 
 impl std::fmt::Display for Duration {
@@ -466,71 +1603,493 @@ impl std::fmt::Display for Duration {
 }
 
 //--------------------------------------------------------------------------------------------------
-// Format enum
+// XAlphabet struct
 
 /**
-Format
+"x" format alphabet
 
-# "a" format
+The alphabet's length is the radix of the encoding; fields that don't fit in a single digit use a
+fixed number of digits.
 
-Four newline-separated timestamps with the epoch time in fractional seconds, RFC 3339 format / UTC,
-default format / UTC, and default format / local
+Alphabet               | Radix | Digits
+-----------------------|-------|-------------------------------------------------------
+[`XAlphabet::standard`] | 60    | `0-9A-Za-x`
+[`XAlphabet::url_safe`] | 64    | `0-9A-Z_a-z~` (RFC 3986 unreserved characters, ASCII order)
+[`XAlphabet::dns`]      | 36    | `0-9a-z` (case-insensitive, for DNS labels)
+[`XAlphabet::new`]      | 2-64  | Unique characters other than whitespace and `-+./:%`
 
-```text
-%s.%f
-%Y-%m-%dT%H:%M:%SZ
-%a %d %b %Y %H:%M:%S %Z # UTC
-%a %d %b %Y %H:%M:%S %Z # Specified or local timezone
 ```
+use dtg_lib::{Dtg, Format, XAlphabet};
 
-# "x" format
-
-Novel UTC / base 60 encoding
+let dtg = Dtg::from("1658448142").unwrap();
 
-```text
-0* 0 1 2 3 4 5 6 7 8 9
-1* A B C D E F G H I J
-2* K L M N O P Q R S T
-3* U V W X Y Z a b c d
-4* e f g h i j k l m n
-5* o p q r s t u v w x
+assert_eq!(dtg.format(&Some(Format::XWith(XAlphabet::standard())), &None), "Xg6L02M");
+assert_eq!(dtg.format(&Some(Format::XWith(XAlphabet::url_safe())), &None), "Vb6L02M");
+assert_eq!(dtg.format(&Some(Format::XWith(XAlphabet::dns())), &None), "1k66l0020m");
 ```
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub struct XAlphabet {
+    digits: Vec<char>,
+    case_insensitive: bool,
+}
 
-Field  | Values           | Result
--------|------------------|----------
-Year   | 2020 => 33*60+40 | Xe
-Month  | Jan-Dec => 0-11  | 0-B
-Day    | 0-27/28/29/30    | 0-R/S/T/U
-Hour   | 0-23             | 0-N
-Minute | 0-59             | 0-x
-Second | 0-59             | 0-x
+impl XAlphabet {
+    /**
+    Create a custom [`XAlphabet`]
 
-See also [`Dtg::from_x`]
+    ```
+    use dtg_lib::XAlphabet;
 
-# Custom format
+    assert_eq!(XAlphabet::new("01").unwrap().radix(), 2);
+    assert_eq!(
+        XAlphabet::new("0120").unwrap_err().to_string(),
+        "Invalid \"x\" format alphabet: duplicate `0`",
+    );
+    ```
 
-See also [`Dtg::format`]
+    # Errors
 
-## Date specifiers
+    Returns an error if the alphabet has fewer than 2 or more than 64 characters, duplicate
+    characters, or `-`, `+`, `.`, `/`, `:`, `%`, whitespace, or control characters (which would
+    clash with "x" format, interval, or `x:ALPHABET` syntax), or if it is the name of a built-in
+    alphabet (`dns`)
+    */
+    pub fn new(s: &str) -> Result<XAlphabet, DtgError> {
+        let invalid = |reason: &str| {
+            DtgError::from(DtgErrorKind::InvalidXAlphabet {
+                reason: reason.to_string(),
+            })
+        };
+        let digits = s.chars().collect::<Vec<char>>();
+        if !(2..=64).contains(&digits.len()) {
+            return Err(invalid("must have 2-64 characters"));
+        }
+        if ["standard", "url-safe", "dns"].contains(&s) {
+            return Err(invalid(&format!("`{s}` is a built-in alphabet name")));
+        }
+        for (i, c) in digits.iter().enumerate() {
+            if ['-', '+', '.', '/', ':', '%'].contains(c) || c.is_whitespace() || c.is_control() {
+                return Err(invalid(&format!("invalid `{c}`")));
+            } else if digits[..i].contains(c) {
+                return Err(invalid(&format!("duplicate `{c}`")));
+            }
+        }
+        Ok(XAlphabet {
+            digits,
+            case_insensitive: false,
+        })
+    }
 
-Spec. | Example       | Description
-------|---------------|----------------------------------------------------------------------------
-`%Y`  | `2001`        | The full proleptic Gregorian year, zero-padded to 4 digits.
-`%C`  | `20`          | The proleptic Gregorian year divided by 100, zero-padded to 2 digits.
-`%y`  | `01`          | The proleptic Gregorian year modulo 100, zero-padded to 2 digits.
-`%m`  | `07`          | Month number (01--12), zero-padded to 2 digits.
-`%b`  | `Jul`         | Abbreviated month name. Always 3 letters.
-`%B`  | `July`        | Full month name. Also accepts corresponding abbreviation in parsing.
-`%h`  | `Jul`         | Same as `%b`.
-`%d`  | `08`          | Day number (01--31), zero-padded to 2 digits.
-`%e`  | ` 8`          | Same as `%d` but space-padded. Same as `%_d`.
-`%a`  | `Sun`         | Abbreviated weekday name. Always 3 letters.
-`%A`  | `Sunday`      | Full weekday name. Also accepts corresponding abbreviation in parsing.
-`%w`  | `0`           | Sunday = 0, Monday = 1, ..., Saturday = 6.
-`%u`  | `7`           | Monday = 1, Tuesday = 2, ..., Sunday = 7. (ISO 8601)
-`%U`  | `28`          | Week number starting with Sunday (00--53), zero-padded to 2 digits.
-`%W`  | `27`          | Same as `%U`, but week 1 starts with the first Monday in that year instead.
-`%G`  | `2001`        | Same as `%Y` but uses the year number in ISO 8601 week date.
+    /**
+    Create the standard base 60 [`XAlphabet`]
+    */
+    #[must_use]
+    pub fn standard() -> XAlphabet {
+        let mut digits = ITOC.iter().map(|(i, c)| (*i, *c)).collect::<Vec<_>>();
+        digits.sort_unstable();
+        XAlphabet {
+            digits: digits.into_iter().map(|(_, c)| c).collect(),
+            case_insensitive: false,
+        }
+    }
+
+    /**
+    Create the URL-safe base 64 [`XAlphabet`]
+    */
+    #[must_use]
+    pub fn url_safe() -> XAlphabet {
+        XAlphabet {
+            digits: ('0'..='9')
+                .chain('A'..='Z')
+                .chain(['_'])
+                .chain('a'..='z')
+                .chain(['~'])
+                .collect(),
+            case_insensitive: false,
+        }
+    }
+
+    /**
+    Create the case-insensitive base 36 [`XAlphabet`] for DNS labels
+
+    Only the digits are valid in a DNS label: the "x" format of a negative year starts with `-`,
+    and the extended "x" format adds a `.` before fractional seconds and a `+` or `-` before an
+    offset, so only the "x" format of years 0-9999, or the extended "x" format in UTC with whole
+    seconds, is a DNS label.
+
+    ```
+    use dtg_lib::{tz, Dtg, Format, XAlphabet};
+
+    let dtg = Dtg::from("1658448142.5").unwrap();
+    let dns = XAlphabet::dns();
+
+    assert_eq!(dtg.format(&Some(Format::XWith(dns.clone())), &None), "1k66l0020m");
+    assert_eq!(
+        dtg.format(&Some(Format::XExtendedWith(dns)), &tz("Asia/Kolkata").ok()),
+        "1k66l50w0m.i+50u",
+    );
+    ```
+    */
+    #[must_use]
+    pub fn dns() -> XAlphabet {
+        XAlphabet {
+            digits: ('0'..='9').chain('a'..='z').collect(),
+            case_insensitive: true,
+        }
+    }
+
+    /**
+    Get the radix
+    */
+    #[must_use]
+    pub fn radix(&self) -> u32 {
+        #[allow(clippy::cast_possible_truncation)]
+        let radix = self.digits.len() as u32;
+        radix
+    }
+
+    /**
+    Get the number of digits needed for values up to `max`
+    */
+    fn width(&self, max: u32) -> usize {
+        let mut width = 1;
+        let mut n = max / self.radix();
+        while n > 0 {
+            width += 1;
+            n /= self.radix();
+        }
+        width
+    }
+
+    /**
+    Get the number of extended "x" format fraction digits: 9 if the radix is a multiple of 10 (so
+    every nanosecond value is exact), otherwise the fewest digits with at least nanosecond precision
+    */
+    fn fraction_digits(&self) -> usize {
+        if self.radix().is_multiple_of(10) {
+            return 9;
+        }
+        let mut digits = 1;
+        let mut scale = u128::from(self.radix());
+        while scale < 1_000_000_000 {
+            digits += 1;
+            scale *= u128::from(self.radix());
+        }
+        digits
+    }
+
+    /**
+    Get the extended "x" format fraction scale (`radix^digits`)
+    */
+    fn fraction_scale(&self) -> u128 {
+        u128::from(self.radix()).pow(u32::try_from(self.fraction_digits()).unwrap())
+    }
+
+    /**
+    Encode a value with at least `width` digits
+    */
+    fn encode(&self, n: u32, width: usize) -> String {
+        self.encode_u128(u128::from(n), width)
+    }
+
+    /**
+    Encode a value with at least `width` digits
+    */
+    fn encode_u128(&self, mut n: u128, width: usize) -> String {
+        let radix = u128::from(self.radix());
+        let mut r = vec![];
+        while n > 0 || r.len() < width {
+            r.push(self.digits[usize::try_from(n % radix).unwrap()]);
+            n /= radix;
+        }
+        r.iter().rev().collect()
+    }
+
+    /**
+    Decode digits
+    */
+    fn decode(&self, chars: &[char]) -> Option<u128> {
+        let mut r: u128 = 0;
+        for c in chars {
+            let c = if self.case_insensitive {
+                c.to_ascii_lowercase()
+            } else {
+                *c
+            };
+            let i = self.digits.iter().position(|x| *x == c)?;
+            r = r
+                .checked_mul(u128::from(self.radix()))?
+                .checked_add(u128::try_from(i).ok()?)?;
+        }
+        Some(r)
+    }
+}
+
+impl Default for XAlphabet {
+    fn default() -> XAlphabet {
+        XAlphabet::standard()
+    }
+}
+
+impl std::fmt::Display for XAlphabet {
+    /**
+    Write the name of a built-in alphabet (`standard`, `url-safe`, `dns`) or the digits
+    */
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if *self == XAlphabet::standard() {
+            write!(f, "standard")
+        } else if *self == XAlphabet::url_safe() {
+            write!(f, "url-safe")
+        } else if *self == XAlphabet::dns() {
+            write!(f, "dns")
+        } else {
+            write!(f, "{}", self.digits.iter().collect::<String>())
+        }
+    }
+}
+
+impl std::str::FromStr for XAlphabet {
+    type Err = DtgError;
+
+    /**
+    Parse the name of a built-in alphabet (`standard`, `url-safe`, `dns`) or custom digits (see
+    [`XAlphabet::new`])
+
+    ```
+    use dtg_lib::XAlphabet;
+
+    assert_eq!("dns".parse::<XAlphabet>().unwrap(), XAlphabet::dns());
+    assert_eq!("01".parse::<XAlphabet>().unwrap().to_string(), "01");
+    assert_eq!("0120".parse::<XAlphabet>().unwrap_err().code(), 109);
+    ```
+    */
+    fn from_str(s: &str) -> Result<XAlphabet, DtgError> {
+        match s {
+            "standard" => Ok(XAlphabet::standard()),
+            "url-safe" => Ok(XAlphabet::url_safe()),
+            "dns" => Ok(XAlphabet::dns()),
+            _ => XAlphabet::new(s),
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Binary clock enums
+
+/**
+Binary clock layout
+
+* `Bcd`: one column per decimal digit (binary coded decimal)
+* `Binary`: one column per field (pure binary)
+
+```
+use dtg_lib::{ClockGlyphs, ClockMode, Dtg, Format};
+
+let dtg = Dtg::from("1658448142").unwrap();
+let fmt = Some(Format::Clock(ClockMode::Bcd, ClockGlyphs::Digits));
+
+assert_eq!(
+    dtg.format(&fmt, &None),
+    "\
+0000  0  0  0  0  0
+0000  1  0  0 00 00
+1011  1 11 00 01 11
+0000 01 00 00 00 00",
+);
+```
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockMode {
+    Bcd,
+    Binary,
+}
+
+/**
+Binary clock glyph set
+
+* `Circles`: `●` / `○`
+* `Squares`: `■` / `□`
+* `Digits`: `1` / `0`
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClockGlyphs {
+    #[default]
+    Circles,
+    Squares,
+    Digits,
+}
+
+impl ClockGlyphs {
+    /**
+    Get the glyph for a bit
+    */
+    #[must_use]
+    pub fn glyph(&self, on: bool) -> char {
+        match (self, on) {
+            (ClockGlyphs::Circles, true) => '●',
+            (ClockGlyphs::Circles, false) => '○',
+            (ClockGlyphs::Squares, true) => '■',
+            (ClockGlyphs::Squares, false) => '□',
+            (ClockGlyphs::Digits, true) => '1',
+            (ClockGlyphs::Digits, false) => '0',
+        }
+    }
+}
+
+impl std::fmt::Display for ClockGlyphs {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ClockGlyphs::Circles => "circles",
+                ClockGlyphs::Squares => "squares",
+                ClockGlyphs::Digits => "digits",
+            },
+        )
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Format enum
+
+/**
+Format
+
+# "a" format
+
+Four newline-separated timestamps with the epoch time in fractional seconds, RFC 3339 format / UTC,
+default format / UTC, and default format / local
+
+```text
+%s.%f
+%Y-%m-%dT%H:%M:%SZ
+%a %d %b %Y %H:%M:%S %Z # UTC
+%a %d %b %Y %H:%M:%S %Z # Specified or local timezone
+```
+
+# "x" format
+
+Novel UTC / base 60 encoding
+
+```text
+0* 0 1 2 3 4 5 6 7 8 9
+1* A B C D E F G H I J
+2* K L M N O P Q R S T
+3* U V W X Y Z a b c d
+4* e f g h i j k l m n
+5* o p q r s t u v w x
+```
+
+Field  | Values           | Result
+-------|------------------|----------
+Year   | 2020 => 33*60+40 | Xe
+Month  | Jan-Dec => 0-11  | 0-B
+Day    | 0-27/28/29/30    | 0-R/S/T/U
+Hour   | 0-23             | 0-N
+Minute | 0-59             | 0-x
+Second | 0-59             | 0-x
+
+[`Format::X`] and [`Format::XExtended`] use [`XAlphabet::default`] and are equal to
+[`Format::XWith`] and [`Format::XExtendedWith`] with it; see [`XAlphabet`] for other alphabets.
+
+See also [`Dtg::from_x`]
+
+# Extended "x" format
+
+The "x" format in a timezone with optional fractional seconds and offset
+
+```text
+[-]YEAR MONTH DAY HOUR MINUTE SECOND[.FRACTION][(+|-)HOUR MINUTE[SECOND]]
+```
+
+* `-`: Negative (BCE) year; also used by the "x" format
+* `FRACTION`: Up to 9 base 60 digits without trailing `0`s, omitted if zero
+* Offset: Base 60 hours, minutes, and seconds if not zero, omitted if zero (UTC)
+
+Field    | Example                         | Result
+---------|---------------------------------|-------
+Fraction | 0.5 s => 30/60                  | .U
+Offset   | +05:30 => +5, 30                | +5U
+Year     | -44 => -(44)                    | -i
+
+Fields are in the given timezone, so `2022-07-22T00:02:22.5Z` in `MST7MDT` is `Xg6KI2M.U-60`, which
+[`Dtg::from_x`] decodes back to the same timestamp.
+
+# Military format
+
+Military date time group in a timezone: day, hour, and minute, the time zone letter, month, and two
+digit year
+
+```text
+DDHHMMZ MON YY
+```
+
+Letter  | Offset
+--------|-----------
+`Z`     | UTC
+`A`-`I` | +1 to +9
+`K`-`M` | +10 to +12
+`N`-`Y` | -1 to -12
+
+Timezones with any other offset at the given time use UTC (`Z`), so `2022-07-22T00:02:22Z` is
+`220002Z JUL 22`, `211802S JUL 22` in `MST7MDT`, and `220002Z JUL 22` in `Asia/Kolkata`.
+
+See also [`Dtg::from_military`]
+
+# Binary clock format
+
+Multi-line binary clock with the same fields as the "bcd" format (century and year, month, day,
+hour, minute, second), most significant bit on top, and a space between fields
+
+`2022-07-22T00:02:22Z` with one column per digit:
+
+```text
+○○○○  ○  ○  ○  ○  ○
+○○○○  ●  ○  ○ ○○ ○○
+●○●●  ● ●● ○○ ○● ●●
+○○○○ ○● ○○ ○○ ○○ ○○
+```
+
+`2022-07-22T00:02:22Z` with one column per field:
+
+```text
+○○
+○○       ○ ○
+●●   ● ○ ○ ●
+○○ ○ ○ ○ ○ ○
+●● ● ● ○ ○ ●
+○● ● ● ○ ● ●
+○○ ● ○ ○ ○ ○
+```
+
+See also [`ClockMode`] and [`ClockGlyphs`]
+
+# Custom format
+
+See also [`Dtg::format`]
+
+## Date specifiers
+
+Spec. | Example       | Description
+------|---------------|----------------------------------------------------------------------------
+`%Y`  | `2001`        | The full proleptic Gregorian year, zero-padded to 4 digits.
+`%C`  | `20`          | The proleptic Gregorian year divided by 100, zero-padded to 2 digits.
+`%y`  | `01`          | The proleptic Gregorian year modulo 100, zero-padded to 2 digits.
+`%m`  | `07`          | Month number (01--12), zero-padded to 2 digits.
+`%b`  | `Jul`         | Abbreviated month name. Always 3 letters.
+`%B`  | `July`        | Full month name. Also accepts corresponding abbreviation in parsing.
+`%h`  | `Jul`         | Same as `%b`.
+`%d`  | `08`          | Day number (01--31), zero-padded to 2 digits.
+`%e`  | ` 8`          | Same as `%d` but space-padded. Same as `%_d`.
+`%a`  | `Sun`         | Abbreviated weekday name. Always 3 letters.
+`%A`  | `Sunday`      | Full weekday name. Also accepts corresponding abbreviation in parsing.
+`%w`  | `0`           | Sunday = 0, Monday = 1, ..., Saturday = 6.
+`%u`  | `7`           | Monday = 1, Tuesday = 2, ..., Sunday = 7. (ISO 8601)
+`%U`  | `28`          | Week number starting with Sunday (00--53), zero-padded to 2 digits.
+`%W`  | `27`          | Same as `%U`, but week 1 starts with the first Monday in that year instead.
+`%G`  | `2001`        | Same as `%Y` but uses the year number in ISO 8601 week date.
 `%g`  | `01`          | Same as `%y` but uses the year number in ISO 8601 week date.
 `%V`  | `27`          | Same as `%U` but uses the week number in ISO 8601 week date (01--53).
 `%j`  | `189`         | Day of the year (001--366), zero-padded to 3 digits.
@@ -539,210 +2098,1878 @@ Spec. | Example       | Description
 `%F`  | `2001-07-08`  | Year-month-day format (ISO 8601). Same as `%Y-%m-%d`.
 `%v`  | ` 8-Jul-2001` | Day-month-year format. Same as `%e-%b-%Y`.
 
-## Time specifiers
+## Time specifiers
+
+Spec.  | Example       | Description
+-------|---------------|----------------------------------------------------------------------
+`%H`   | `00`          | Hour number (00--23), zero-padded to 2 digits.
+`%k`   | ` 0`          | Same as `%H` but space-padded. Same as `%_H`.
+`%I`   | `12`          | Hour number in 12-hour clocks (01--12), zero-padded to 2 digits.
+`%l`   | `12`          | Same as `%I` but space-padded. Same as `%_I`.
+`%P`   | `am`          | `am` or `pm` in 12-hour clocks.
+`%p`   | `AM`          | `AM` or `PM` in 12-hour clocks.
+`%M`   | `34`          | Minute number (00--59), zero-padded to 2 digits.
+`%S`   | `60`          | Second number (00--60), zero-padded to 2 digits.
+`%f`   | `026490000`   | The fractional seconds (in nanoseconds) since last whole second.
+`%.f`  | `.026490`     | Similar to `.%f` but left-aligned. These all consume the leading dot.
+`%.3f` | `.026`        | Similar to `.%f` but left-aligned but fixed to a length of 3.
+`%.6f` | `.026490`     | Similar to `.%f` but left-aligned but fixed to a length of 6.
+`%.9f` | `.026490000`  | Similar to `.%f` but left-aligned but fixed to a length of 9.
+`%3f`  | `026`         | Similar to `%.3f` but without the leading dot.
+`%6f`  | `026490`      | Similar to `%.6f` but without the leading dot.
+`%9f`  | `026490000`   | Similar to `%.9f` but without the leading dot.
+`%R`   | `00:34`       | Hour-minute format. Same as `%H:%M`.
+`%T`   | `00:34:60`    | Hour-minute-second format. Same as `%H:%M:%S`.
+`%X`   | `00:34:60`    | Locale's time representation (e.g., 23:13:48).
+`%r`   | `12:34:60 AM` | Hour-minute-second format in 12-hour clocks. Same as `%I:%M:%S %p`.
+
+## Time zone specifiers
+
+Spec. | Example  | Description
+------|----------|--------------------------------------------------------------------------
+`%Z`  | `ACST`   | Local time zone name. Skips all non-whitespace characters during parsing.
+`%z`  | `+0930`  | Offset from the local time to UTC (with UTC being `+0000`).
+`%:z` | `+09:30` | Same as `%z` but with a colon.
+`%#z` | `+09`    | *Parsing only:* Same as `%z` but allows minutes to be missing or present.
+
+## Date & time specifiers
+
+Spec. | Example                            | Description
+------|------------------------------------|------------------------------------------------------
+`%c`  | `Sun Jul  8 00:34:60 2001`         | Locale's date and time (like Thu Mar  3 23:05:25 2005).
+`%+`  | `2001-07-08T00:34:60.026490+09:30` | ISO 8601 / RFC 3339 date & time format.
+`%s`  | `994518299`                        | UNIX timestamp (seconds since 1970-01-01 00:00 UTC).
+
+## Special specifiers
+
+Spec. | Description
+------|------------------------
+`%t`  | Literal tab (`\t`).
+`%n`  | Literal newline (`\n`).
+`%%`  | Literal percent sign.
+*/
+#[derive(Clone, Debug)]
+pub enum Format {
+    A,
+    BCD,
+    Clock(ClockMode, ClockGlyphs),
+    Military,
+    X,
+    XExtended,
+    XWith(XAlphabet),
+    XExtendedWith(XAlphabet),
+    Custom(String),
+}
+
+impl std::cmp::PartialEq for Format {
+    /**
+    Formats are equal if their variants and values are equal, except that [`Format::X`] and
+    [`Format::XExtended`] are equal to [`Format::XWith`] and [`Format::XExtendedWith`] with the
+    default alphabet
+    */
+    fn eq(&self, other: &Format) -> bool {
+        match (self.x_parts(), other.x_parts()) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => match (self, other) {
+                (Format::A, Format::A)
+                | (Format::BCD, Format::BCD)
+                | (Format::Military, Format::Military) => true,
+                (Format::Clock(a, b), Format::Clock(c, d)) => a == c && b == d,
+                (Format::Custom(a), Format::Custom(b)) => a == b,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl Format {
+    /**
+    Create a default [Format]
+    */
+    #[must_use]
+    pub fn new() -> Format {
+        Format::Custom(DEFAULT.to_string())
+    }
+
+    /**
+    Create an RFC 3339 [Format]
+    */
+    #[must_use]
+    pub fn rfc_3339() -> Format {
+        Format::Custom(RFC_3339.to_string())
+    }
+
+    /**
+    Create a custom [Format]
+    */
+    #[must_use]
+    pub fn custom(s: &str) -> Format {
+        Format::Custom(s.to_string())
+    }
+
+    /**
+    Create a custom [Format], checking its specifiers (see [`Format::validate`])
+
+    ```
+    use dtg_lib::{DtgErrorKind, Format};
+
+    assert_eq!(Format::compile("%Y-%m-%d").unwrap(), Format::custom("%Y-%m-%d"));
+    assert_eq!(
+        Format::compile("%Y %K").unwrap_err().kind(),
+        &DtgErrorKind::InvalidFormatSpecifier {
+            format: "%Y %K".into(),
+            specifier: "%K".into(),
+            position: 3,
+        },
+    );
+    ```
+
+    # Errors
+
+    Returns an error if the format has an invalid or incomplete specifier
+    */
+    pub fn compile(s: &str) -> Result<Format, DtgError> {
+        let fmt = Format::custom(s);
+        fmt.validate()?;
+        Ok(fmt)
+    }
+
+    /**
+    Check the specifiers of a custom [Format] (other formats are always valid)
+
+    A specifier is a `%`, optional flags (`-`, `_`, `0`, `^`, `#`), width, precision (`.N`), and
+    colons, then a conversion char; see the tables above
+
+    ```
+    use dtg_lib::Format;
+
+    assert!(Format::custom("%A %-d %B %::z %.3f %%").validate().is_ok());
+    assert_eq!(
+        Format::custom("%Y%").validate().unwrap_err().to_string(),
+        "Invalid format specifier `%` at position 2: `%Y%`",
+    );
+    ```
+
+    # Errors
+
+    Returns an error with the first invalid or incomplete specifier and its char position
+    */
+    pub fn validate(&self) -> Result<(), DtgError> {
+        let Format::Custom(f) = self else {
+            return Ok(());
+        };
+        let sample = Timestamp::UNIX_EPOCH.to_zoned(TimeZone::UTC);
+        let chars = f.chars().collect::<Vec<char>>();
+        let mut i = 0;
+        while i < chars.len() {
+            if chars[i] != '%' {
+                i += 1;
+                continue;
+            }
+            let mut j = i + 1;
+            while chars
+                .get(j)
+                .is_some_and(|c| "-_0^#.:".contains(*c) || c.is_ascii_digit())
+            {
+                j += 1;
+            }
+            let end = (j + 1).min(chars.len());
+            let specifier = chars[i..end].iter().collect::<String>();
+            if j == chars.len() || jiff::fmt::strtime::format(&specifier, &sample).is_err() {
+                return Err(DtgErrorKind::InvalidFormatSpecifier {
+                    format: f.clone(),
+                    specifier,
+                    position: i,
+                }
+                .into());
+            }
+            i = end;
+        }
+        Ok(())
+    }
+
+    /**
+    Format a [Timestamp] with a timezone
+    */
+    fn with(&self, dt: &Timestamp, tz: &TimeZone) -> Result<String, DtgError> {
+        Ok(match self {
+            Format::Custom(f) => {
+                if f == "%s" {
+                    format!("{}", dt.as_second())
+                } else if f == EPOCH {
+                    format!("{}.{}", dt.as_second(), dt.subsec_nanosecond())
+                } else if f == RFC_3339 {
+                    dt.strftime(f).to_string()
+                } else {
+                    let invalid = |e| {
+                        DtgError::from(DtgErrorKind::InvalidFormat { input: f.clone() })
+                            .with_source(e)
+                    };
+                    let mut f = f.clone();
+                    if f.contains("%n") {
+                        f = f.replace("%n", "\n");
+                    }
+                    if f.contains("%f") {
+                        f = f.replace("%f", &format!("{:09}", dt.subsec_nanosecond()));
+                    }
+                    jiff::fmt::strtime::format(&f, &dt.to_zoned(tz.clone())).map_err(invalid)?
+                }
+            }
+            Format::A => {
+                let epoch_seconds = i128::from(dt.as_second());
+                let epoch_nanoseconds = dt.as_nanosecond() - epoch_seconds * 1_000_000_000;
+                let epoch = format!("{epoch_seconds}.{epoch_nanoseconds:09}");
+                let rfc = dt.strftime(RFC_3339).to_string();
+                let default = dt.to_zoned(TimeZone::UTC).strftime(DEFAULT).to_string();
+                let zoned_default = dt.to_zoned(tz.clone()).strftime(DEFAULT).to_string();
+                [epoch, rfc, default, zoned_default].join("\n")
+            }
+            Format::X => Format::x(dt, tz, false, &XAlphabet::default()),
+            Format::XExtended => Format::x(dt, tz, true, &XAlphabet::default()),
+            Format::XWith(alphabet) => Format::x(dt, tz, false, alphabet),
+            Format::XExtendedWith(alphabet) => Format::x(dt, tz, true, alphabet),
+            Format::BCD => Format::bcd(dt, tz),
+            Format::Clock(mode, glyphs) => Format::clock(dt, tz, *mode, *glyphs),
+            Format::Military => Format::military(dt, tz),
+        })
+    }
+
+    /**
+    Get whether an "x" format is extended and its alphabet
+    */
+    fn x_parts(&self) -> Option<(bool, XAlphabet)> {
+        match self {
+            Format::X => Some((false, XAlphabet::default())),
+            Format::XExtended => Some((true, XAlphabet::default())),
+            Format::XWith(alphabet) => Some((false, alphabet.clone())),
+            Format::XExtendedWith(alphabet) => Some((true, alphabet.clone())),
+            _ => None,
+        }
+    }
+
+    /**
+    Format a [Timestamp] as a military date time group
+    */
+    fn military(dt: &Timestamp, tz: &TimeZone) -> String {
+        let seconds = tz.to_offset(*dt).seconds();
+        let zone = MILITARY_ZONES
+            .iter()
+            .find(|(_, _, hours)| i32::from(*hours) * 3600 == seconds);
+        let (letter, tz) = match zone {
+            Some((letter, _, _)) => (*letter, tz.clone()),
+            None => ('Z', TimeZone::UTC),
+        };
+        let dt = dt.to_zoned(tz);
+        format!(
+            "{}{letter} {}",
+            dt.strftime("%d%H%M"),
+            dt.strftime("%b %y").to_string().to_uppercase(),
+        )
+    }
+
+    /**
+    Format a [Timestamp] with "x" format, or with extended "x" format in a timezone
+    */
+    fn x(dt: &Timestamp, tz: &TimeZone, extended: bool, alphabet: &XAlphabet) -> String {
+        let dt = dt.to_zoned(if extended { tz.clone() } else { TimeZone::UTC });
+        let mut r = if dt.year() < 0 {
+            String::from("-")
+        } else {
+            String::new()
+        };
+        r.push_str(&alphabet.encode(u32::from(dt.year().unsigned_abs()), 1));
+        #[allow(clippy::cast_sign_loss)]
+        for (n, (_, max)) in [
+            dt.month() - 1,
+            dt.day() - 1,
+            dt.hour(),
+            dt.minute(),
+            dt.second(),
+        ]
+        .iter()
+        .zip(X_FIELDS)
+        {
+            r.push_str(&alphabet.encode(*n as u32, alphabet.width(max)));
+        }
+        if extended {
+            let nanosecond = dt.subsec_nanosecond().unsigned_abs();
+            if nanosecond != 0 {
+                let units = x_fraction_units(nanosecond, alphabet);
+                r.push('.');
+                r.push_str(
+                    alphabet
+                        .encode_u128(units, alphabet.fraction_digits())
+                        .trim_end_matches(alphabet.digits[0]),
+                );
+            }
+            let offset = dt.offset().seconds();
+            if offset != 0 {
+                r.push(if offset < 0 { '-' } else { '+' });
+                let offset = offset.unsigned_abs();
+                let (hours, minutes, seconds) = (offset / 3600, offset / 60 % 60, offset % 60);
+                r.push_str(&alphabet.encode(hours, alphabet.width(25)));
+                r.push_str(&alphabet.encode(minutes, alphabet.width(59)));
+                if seconds != 0 {
+                    r.push_str(&alphabet.encode(seconds, alphabet.width(59)));
+                }
+            }
+        }
+        r
+    }
+
+    /**
+    Format a [Timestamp] like a binary clock using the Braille Patterns Unicode Block and `|`
+    separators
+    */
+    fn bcd(dt: &Timestamp, tz: &TimeZone) -> String {
+        let (negative, fields) = Format::bcd_fields(dt, tz);
+        let mut r = if negative {
+            String::from("-")
+        } else {
+            String::new()
+        };
+        for (i, n) in fields.iter().enumerate() {
+            if i >= 2 {
+                r.push('|');
+            }
+            r.push(bbd_lib::encode_bcd(*n));
+        }
+        r
+    }
+
+    /**
+    Format a [Timestamp] like a multi-line binary clock
+    */
+    fn clock(dt: &Timestamp, tz: &TimeZone, mode: ClockMode, glyphs: ClockGlyphs) -> String {
+        fn bits(max: u8) -> u32 {
+            u8::BITS - max.leading_zeros()
+        }
+
+        let (negative, fields) = Format::bcd_fields(dt, tz);
+
+        // Groups of (value, max) columns; century and year share a group
+        let mut groups: Vec<Vec<(u8, u8)>> = vec![vec![]];
+        for (i, (n, (_, _, max))) in fields.iter().zip(BCD_FIELDS).enumerate() {
+            if i >= 2 {
+                groups.push(vec![]);
+            }
+            let group = groups.last_mut().unwrap();
+            match mode {
+                ClockMode::Bcd => {
+                    group.push((n / 10, max / 10));
+                    group.push((n % 10, 9));
+                }
+                ClockMode::Binary => group.push((*n, max)),
+            }
+        }
+
+        let rows = groups
+            .iter()
+            .flatten()
+            .map(|(_, max)| bits(*max))
+            .max()
+            .unwrap();
+        (0..rows)
+            .rev()
+            .map(|bit| {
+                let mut line = if negative {
+                    String::from(if bit == 0 { "-" } else { " " })
+                } else {
+                    String::new()
+                };
+                for (i, group) in groups.iter().enumerate() {
+                    if i > 0 {
+                        line.push(' ');
+                    }
+                    for (n, max) in group {
+                        line.push(if bit < bits(*max) {
+                            glyphs.glyph(n >> bit & 1 == 1)
+                        } else {
+                            ' '
+                        });
+                    }
+                }
+                line.trim_end().to_string()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /**
+    Get the negative year flag and century, year, month, day, hour, minute, and second fields of a
+    [Timestamp] in a timezone
+    */
+    fn bcd_fields(dt: &Timestamp, tz: &TimeZone) -> (bool, [u8; 7]) {
+        let dt = dt.to_zoned(tz.clone());
+        let yyyy = dt.year();
+        #[allow(clippy::cast_sign_loss)]
+        let (negative, yyyy) = if yyyy < 0 {
+            (true, (-yyyy) as u32)
+        } else {
+            (false, yyyy as u32)
+        };
+        let cc = u8::try_from(yyyy / 100).unwrap();
+        let yy = u8::try_from(yyyy - yyyy / 100 * 100).unwrap();
+        #[allow(clippy::cast_sign_loss)]
+        (
+            negative,
+            [
+                cc,
+                yy,
+                dt.month() as u8,
+                dt.day() as u8,
+                dt.hour() as u8,
+                dt.minute() as u8,
+                dt.second() as u8,
+            ],
+        )
+    }
+}
+
+impl Default for Format {
+    fn default() -> Format {
+        Format::new()
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// FormatRegistry struct
+
+/**
+Named [Format] with its aliases and a description
+
+See also [`FormatRegistry`]
+*/
+#[derive(Clone, Debug)]
+pub struct NamedFormat {
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub description: String,
+    pub format: Format,
+}
+
+/**
+Named formats: the built-in names and aliases followed by any registered
+
+Name                | Aliases                | Format
+--------------------|------------------------|---------------------------------------------------
+`all`               | `a`                    | [`Format::A`]
+`bcd`               |                        | [`Format::BCD`]
+`bcd-clock`         |                        | [`Format::Clock`] with [`ClockMode::Bcd`]
+`binary-clock`      |                        | [`Format::Clock`] with [`ClockMode::Binary`]
+`compact-date`      | `cd`                   | `%Y%m%d`
+`compact-date-time` | `cdt`                  | `%Y%m%d-%H%M%S`
+`compact-time`      | `ct`                   | `%H%M%S`
+`default`           | `d`                    | [`Format::default`]
+`dtg`               |                        | [`Format::Military`]
+`rfc-3339`          | `i`, `r`, `iso`, `rfc` | [`Format::rfc_3339`]
+`x`                 |                        | [`Format::XWith`]
+`x-extended`        | `xe`                   | [`Format::XExtendedWith`]
+
+```
+use dtg_lib::{Dtg, Format, FormatRegistry};
+
+let dtg = Dtg::from("1658448142").unwrap();
+let mut registry = FormatRegistry::new();
+
+assert_eq!(dtg.format(&registry.get("cd").cloned(), &None), "20220722");
+assert_eq!(dtg.format(&registry.get("dtg").cloned(), &None), "220002Z JUL 22");
+assert!(registry.get("stamp").is_none());
+
+registry.register("stamp", Format::custom("%Y%m%d %H:%M"), "Stamp").unwrap();
+
+assert_eq!(dtg.format(&registry.get("stamp").cloned(), &None), "20220722 00:02");
+assert_eq!(registry.list().last().unwrap().description, "Stamp");
+assert_eq!(registry.register("cd", Format::custom("%F"), "").unwrap_err().code(), 117);
+```
+*/
+#[derive(Clone, Debug)]
+pub struct FormatRegistry {
+    formats: Vec<NamedFormat>,
+    builtin: usize,
+}
+
+impl FormatRegistry {
+    /**
+    Create a [`FormatRegistry`] with the built-in named formats
+    */
+    #[must_use]
+    pub fn new() -> FormatRegistry {
+        FormatRegistry::new_with(&XAlphabet::default(), ClockGlyphs::default())
+    }
+
+    /**
+    Create a [`FormatRegistry`] with the built-in named formats using an "x" format alphabet and a
+    binary clock glyph set
+    */
+    #[must_use]
+    pub fn new_with(alphabet: &XAlphabet, glyphs: ClockGlyphs) -> FormatRegistry {
+        let formats = [
+            (
+                "all",
+                &["a"][..],
+                "\"a\" format: epoch, RFC 3339 / UTC, default / UTC, and default / timezone",
+                Format::A,
+            ),
+            (
+                "bcd",
+                &[],
+                "\"bcd\" format: binary clock with Braille Patterns",
+                Format::BCD,
+            ),
+            (
+                "bcd-clock",
+                &[],
+                "Binary clock with one column per digit",
+                Format::Clock(ClockMode::Bcd, glyphs),
+            ),
+            (
+                "binary-clock",
+                &[],
+                "Binary clock with one column per field",
+                Format::Clock(ClockMode::Binary, glyphs),
+            ),
+            ("compact-date", &["cd"], "%Y%m%d", Format::custom("%Y%m%d")),
+            (
+                "compact-date-time",
+                &["cdt"],
+                "%Y%m%d-%H%M%S",
+                Format::custom("%Y%m%d-%H%M%S"),
+            ),
+            ("compact-time", &["ct"], "%H%M%S", Format::custom("%H%M%S")),
+            ("default", &["d"], DEFAULT, Format::default()),
+            (
+                "dtg",
+                &[],
+                "Military date time group (DDHHMMZ MON YY)",
+                Format::Military,
+            ),
+            (
+                "rfc-3339",
+                &["i", "r", "iso", "rfc"],
+                RFC_3339,
+                Format::rfc_3339(),
+            ),
+            (
+                "x",
+                &[],
+                "\"x\" format: novel UTC / base 60 encoding",
+                Format::XWith(alphabet.clone()),
+            ),
+            (
+                "x-extended",
+                &["xe"],
+                "Extended \"x\" format: timezone, fractional seconds, and offset",
+                Format::XExtendedWith(alphabet.clone()),
+            ),
+        ]
+        .into_iter()
+        .map(|(name, aliases, description, format)| NamedFormat {
+            name: name.to_string(),
+            aliases: aliases.iter().map(ToString::to_string).collect(),
+            description: description.to_string(),
+            format,
+        })
+        .collect::<Vec<NamedFormat>>();
+        FormatRegistry {
+            builtin: formats.len(),
+            formats,
+        }
+    }
+
+    /**
+    Register a named format, replacing any registered with the same name
+
+    # Errors
+
+    Returns an error if the name is empty or a built-in name or alias
+    */
+    pub fn register(
+        &mut self,
+        name: &str,
+        format: Format,
+        description: &str,
+    ) -> Result<(), DtgError> {
+        if name.is_empty() || self.formats[..self.builtin].iter().any(|x| x.is(name)) {
+            return Err(DtgErrorKind::InvalidNamedFormat {
+                name: name.to_string(),
+            }
+            .into());
+        }
+        let named = NamedFormat {
+            name: name.to_string(),
+            aliases: vec![],
+            description: description.to_string(),
+            format,
+        };
+        match self.formats.iter_mut().find(|x| x.name == name) {
+            Some(x) => *x = named,
+            None => self.formats.push(named),
+        }
+        Ok(())
+    }
+
+    /**
+    Get a [Format] by name or alias
+    */
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Format> {
+        self.formats.iter().find(|x| x.is(name)).map(|x| &x.format)
+    }
+
+    /**
+    List the named formats
+    */
+    #[must_use]
+    pub fn list(&self) -> &[NamedFormat] {
+        &self.formats
+    }
+}
+
+impl Default for FormatRegistry {
+    fn default() -> FormatRegistry {
+        FormatRegistry::new()
+    }
+}
+
+impl NamedFormat {
+    /**
+    Whether a name is the name or one of the aliases
+    */
+    fn is(&self, name: &str) -> bool {
+        self.name == name || self.aliases.iter().any(|x| x == name)
+    }
+}
+
+impl std::fmt::Display for Format {
+    /**
+    Write the built-in name (see [`FormatRegistry`]) with any non-default "x" format alphabet or
+    binary clock glyph set after a `:`, or the custom format (with a `custom:` prefix if it would
+    otherwise parse as a built-in format)
+
+    ```
+    use dtg_lib::{ClockGlyphs, ClockMode, Format, XAlphabet};
+
+    assert_eq!(Format::XExtended.to_string(), "x-extended");
+    assert_eq!(Format::XWith(XAlphabet::dns()).to_string(), "x:dns");
+    assert_eq!(Format::Clock(ClockMode::Bcd, ClockGlyphs::Digits).to_string(), "bcd-clock:digits");
+    assert_eq!(Format::custom("%H:%M").to_string(), "%H:%M");
+    assert_eq!(Format::custom("x:dns").to_string(), "custom:x:dns");
+    ```
+    */
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Format::X | Format::XExtended | Format::XWith(_) | Format::XExtendedWith(_) => {
+                let (extended, alphabet) = self.x_parts().unwrap_or_default();
+                write!(f, "{}", if extended { "x-extended" } else { "x" })?;
+                if alphabet != XAlphabet::default() {
+                    write!(f, ":{alphabet}")?;
+                }
+                Ok(())
+            }
+            Format::Clock(mode, glyphs) => {
+                write!(
+                    f,
+                    "{}",
+                    match mode {
+                        ClockMode::Bcd => "bcd-clock",
+                        ClockMode::Binary => "binary-clock",
+                    },
+                )?;
+                if *glyphs != ClockGlyphs::default() {
+                    write!(f, ":{glyphs}")?;
+                }
+                Ok(())
+            }
+            Format::A => write!(f, "all"),
+            Format::BCD => write!(f, "bcd"),
+            Format::Military => write!(f, "dtg"),
+            Format::Custom(s) => {
+                let named = s.split_once(':').is_some_and(|(name, parameter)| {
+                    name == "custom"
+                        || (!parameter.contains('%')
+                            && ["x", "x-extended", "xe", "bcd-clock", "binary-clock"]
+                                .contains(&name))
+                });
+                if named || FormatRegistry::new().get(s).is_some() {
+                    write!(f, "custom:")?;
+                }
+                write!(f, "{s}")
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for Format {
+    type Err = DtgError;
+
+    /**
+    Parse a built-in named format (see [`FormatRegistry`]), optionally with an "x" format alphabet
+    (see [`XAlphabet`]) or binary clock glyph set after a `:` (like `x:dns` or `bcd-clock:digits`),
+    or else a custom format (see [`Format::compile`]), which may have a `custom:` prefix
+
+    ```
+    use dtg_lib::{Dtg, Format, XAlphabet};
+
+    let dtg = Dtg::from("1658448142").unwrap();
+    let fmt = "compact-date".parse::<Format>().unwrap();
+
+    assert_eq!(dtg.format(&Some(fmt), &None), "20220722");
+    assert_eq!(dtg.format(&"%H:%M".parse().ok(), &None), "00:02");
+    assert_eq!("xe:dns".parse::<Format>().unwrap(), Format::XExtendedWith(XAlphabet::dns()));
+    assert_eq!("literal".parse::<Format>().unwrap(), Format::custom("literal"));
+    assert_eq!("custom:dtg".parse::<Format>().unwrap(), Format::custom("dtg"));
+    assert!(matches!("%K".parse::<Format>(), Err(e) if e.code() == 119));
+    assert!(matches!("x:0120".parse::<Format>(), Err(e) if e.code() == 109));
+    ```
+
+    # Errors
+
+    Returns an error if the alphabet, glyph set, or custom format is invalid
+    */
+    fn from_str(s: &str) -> Result<Format, DtgError> {
+        if let Some(custom) = s.strip_prefix("custom:") {
+            return Format::compile(custom);
+        }
+        if let Some((name, parameter)) = s.split_once(':')
+            && !parameter.contains('%')
+        {
+            let clock = |mode| {
+                [
+                    ClockGlyphs::Circles,
+                    ClockGlyphs::Squares,
+                    ClockGlyphs::Digits,
+                ]
+                .into_iter()
+                .find(|x| x.to_string() == parameter)
+                .map(|glyphs| Format::Clock(mode, glyphs))
+                .ok_or_else(|| {
+                    DtgError::from(DtgErrorKind::InvalidFormat {
+                        input: s.to_string(),
+                    })
+                })
+            };
+            match name {
+                "x" => return parameter.parse().map(Format::XWith),
+                "x-extended" | "xe" => return parameter.parse().map(Format::XExtendedWith),
+                "bcd-clock" => return clock(ClockMode::Bcd),
+                "binary-clock" => return clock(ClockMode::Binary),
+                _ => {}
+            }
+        }
+        match FormatRegistry::new().get(s) {
+            Some(format) => Ok(format.clone()),
+            None => Format::compile(s),
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// TzAbbreviation struct
+
+/**
+Candidate zone for a timezone abbreviation
+
+See also [`tz_abbreviations`]
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub struct TzAbbreviation {
+    pub zone: String,
+    pub offset: Offset,
+    pub dst: bool,
+}
+
+impl std::fmt::Display for TzAbbreviation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} ({})", self.zone, self.offset)
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// ZoneInfo struct
+
+/**
+Timezone offset, abbreviation, DST state, and next transition at an instant
+
+```
+use dtg_lib::{Dtg, ZoneInfo};
+
+let dtg = Dtg::from("1658448142").unwrap();
+let info = ZoneInfo::new("America/New_York", &dtg).unwrap();
+
+assert_eq!(info.name, "America/New_York");
+assert_eq!(info.offset.seconds(), -4 * 3600);
+assert_eq!(info.abbreviation, "EDT");
+assert!(info.dst);
+assert_eq!(info.next_transition.unwrap().rfc_3339(), "2022-11-06T06:00:00Z");
+assert_eq!(dtg.default(&Some(info.timezone)), "Thu 21 Jul 2022 20:02:22 EDT");
+```
+*/
+#[derive(Debug)]
+pub struct ZoneInfo {
+    pub name: String,
+    pub timezone: TimeZone,
+    pub offset: Offset,
+    pub abbreviation: String,
+    pub dst: bool,
+    pub next_transition: Option<Dtg>,
+}
+
+impl ZoneInfo {
+    /**
+    Get the [`ZoneInfo`] for a timezone name (see [tz]) at a [Dtg]
+
+    # Errors
+
+    Returns an error if not able to get the timezone
+    */
+    pub fn new(name: &str, dtg: &Dtg) -> Result<ZoneInfo, DtgError> {
+        let timezone = tz(name)?;
+        let info = timezone.to_offset_info(dtg.dt);
+        let (offset, abbreviation, dst) = (
+            info.offset(),
+            info.abbreviation().to_string(),
+            info.dst().is_dst(),
+        );
+        let next_transition = timezone
+            .following(dtg.dt)
+            .find(|t| {
+                t.offset() != offset || t.abbreviation() != abbreviation || t.dst().is_dst() != dst
+            })
+            .map(|t| Dtg { dt: t.timestamp() });
+        Ok(ZoneInfo {
+            name: name.to_string(),
+            timezone,
+            offset,
+            abbreviation,
+            dst,
+            next_transition,
+        })
+    }
+
+    /**
+    Get the [`ZoneInfo`] at a [Dtg] for each timezone name (see [`tz_names`]) that matches a search
+    (see [`tz_search`]), is in a country (see [`tz_country`]), and has an offset
+
+    Zones are ranked if searching, otherwise in the country's order, otherwise sorted by name.
+
+    ```
+    use dtg_lib::{Dtg, Offset, ZoneInfo};
+
+    let dtg = Dtg::from("1658448142").unwrap();
+    let names = |zones: Vec<ZoneInfo>| zones.into_iter().map(|x| x.name).collect::<Vec<String>>();
+
+    assert_eq!(
+        names(ZoneInfo::list(Some("new york"), None, None, &dtg)),
+        ["America/New_York"],
+    );
+    assert_eq!(
+        names(ZoneInfo::list(Some("asia"), None, Offset::from_seconds(20_700).ok(), &dtg)),
+        ["Asia/Kathmandu", "Asia/Katmandu"],
+    );
+    assert_eq!(
+        names(ZoneInfo::list(None, Some("NP"), None, &dtg)),
+        ["Asia/Kathmandu"],
+    );
+    ```
+    */
+    #[must_use]
+    pub fn list(
+        search: Option<&str>,
+        country: Option<&str>,
+        offset: Option<Offset>,
+        dtg: &Dtg,
+    ) -> Vec<ZoneInfo> {
+        let country = country.map(tz_country);
+        let names = match (search, &country) {
+            (Some(search), _) => tz_search(search),
+            (None, Some(country)) => country.clone(),
+            (None, None) => tz_names(),
+        };
+        names
+            .into_iter()
+            .filter(|name| {
+                country
+                    .as_ref()
+                    .is_none_or(|country| country.contains(name))
+            })
+            .filter_map(|name| ZoneInfo::new(&name, dtg).ok())
+            .filter(|info| offset.is_none_or(|offset| info.offset == offset))
+            .collect()
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// ZoneTransition struct
+
+/**
+Timezone UTC offset and/or abbreviation change
+
+```
+use dtg_lib::{tz, Dtg, ZoneTransition};
+
+let from = Dtg::from_ymd_hms(2022, 1, 1, 0, 0, 0).unwrap();
+let to = Dtg::from_ymd_hms(2023, 1, 1, 0, 0, 0).unwrap();
+let transitions = ZoneTransition::list(&tz("America/New_York").unwrap(), &from, &to);
+
+assert_eq!(transitions.len(), 2);
+assert_eq!(transitions[0].dtg.rfc_3339(), "2022-03-13T07:00:00Z");
+assert_eq!(transitions[0].offset_before.seconds(), -5 * 3600);
+assert_eq!(transitions[0].abbreviation_before, "EST");
+assert_eq!(transitions[0].offset_after.seconds(), -4 * 3600);
+assert_eq!(transitions[0].abbreviation_after, "EDT");
+assert!(transitions[0].dst);
+assert_eq!(transitions[1].dtg.rfc_3339(), "2022-11-06T06:00:00Z");
+```
+*/
+#[derive(Debug)]
+pub struct ZoneTransition {
+    pub dtg: Dtg,
+    pub offset_before: Offset,
+    pub abbreviation_before: String,
+    pub offset_after: Offset,
+    pub abbreviation_after: String,
+    pub dst: bool,
+}
+
+impl ZoneTransition {
+    /**
+    Get every UTC offset and/or abbreviation change of a timezone after one [Dtg] until another
+    */
+    #[must_use]
+    pub fn list(tz: &TimeZone, from: &Dtg, to: &Dtg) -> Vec<ZoneTransition> {
+        let info = tz.to_offset_info(from.dt);
+        let (mut offset, mut abbreviation) = (info.offset(), info.abbreviation().to_string());
+        let mut r = vec![];
+        for t in tz.following(from.dt).take_while(|t| t.timestamp() <= to.dt) {
+            if t.offset() == offset && t.abbreviation() == abbreviation {
+                continue;
+            }
+            r.push(ZoneTransition {
+                dtg: Dtg { dt: t.timestamp() },
+                offset_before: offset,
+                abbreviation_before: abbreviation,
+                offset_after: t.offset(),
+                abbreviation_after: t.abbreviation().to_string(),
+                dst: t.dst().is_dst(),
+            });
+            (offset, abbreviation) = (t.offset(), t.abbreviation().to_string());
+        }
+        r
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// WorkingHours struct
+
+/**
+Local working hours for meeting planning (see [`MeetingSlot`])
+
+```
+use dtg_lib::WorkingHours;
+
+let hours = WorkingHours::new("8:30-17").unwrap();
+
+assert_eq!((hours.start, hours.end), (510, 1020));
+assert_eq!(WorkingHours::default(), WorkingHours::new("09:00-17:00").unwrap());
+assert_eq!(WorkingHours::new("0-24").unwrap().end, 1440);
+assert_eq!(WorkingHours::new("22-06").unwrap().end, 360);
+assert_eq!(WorkingHours::new("9-9").unwrap_err().code(), 116);
+```
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WorkingHours {
+    /// Start (minutes after midnight)
+    pub start: u16,
+
+    /// End (minutes after midnight; before the start for overnight hours)
+    pub end: u16,
+}
+
+impl WorkingHours {
+    /**
+    Create working hours from `H[H][:MM]-H[H][:MM]`, wrapping around midnight if the end is before
+    the start (like `22-06`)
+
+    # Errors
+
+    Returns an error if the start or end is not a valid time (up to `24:00`), the end is the start,
+    or the start is `24:00` for overnight hours
+    */
+    pub fn new(s: &str) -> Result<WorkingHours, DtgError> {
+        let e = || {
+            DtgError::from(DtgErrorKind::InvalidWorkingHours {
+                input: s.to_string(),
+            })
+        };
+        let minutes = |t: &str| {
+            let (h, m) = t.trim().split_once(':').unwrap_or((t.trim(), "00"));
+            if h.is_empty() || h.len() > 2 || m.len() != 2 {
+                return None;
+            }
+            let (h, m) = (h.parse::<u16>().ok()?, m.parse::<u16>().ok()?);
+            (m < 60 && h * 60 + m <= 1440).then_some(h * 60 + m)
+        };
+        let (start, end) = s.split_once('-').ok_or_else(e)?;
+        match (minutes(start), minutes(end)) {
+            (Some(start), Some(end)) if start < end || (end < start && start < 1440) => {
+                Ok(WorkingHours { start, end })
+            }
+            _ => Err(e()),
+        }
+    }
+
+    /**
+    Whether the hour starting at a local time is within the working hours
+    */
+    fn contains(self, time: Time) -> bool {
+        let m = u16::try_from(i32::from(time.hour()) * 60 + i32::from(time.minute())).unwrap();
+        if self.start < self.end {
+            self.start <= m && m + 60 <= self.end
+        } else {
+            (self.start <= m && m + 60 <= self.end + 1440) || m + 60 <= self.end
+        }
+    }
+
+    /**
+    Distance in minutes (around the clock) between the middle of the hour starting at a local time
+    and the middle of the working hours
+    */
+    fn distance(self, time: Time) -> u32 {
+        let m =
+            u32::from(time.hour().unsigned_abs()) * 60 + u32::from(time.minute().unsigned_abs());
+        let mut middle = u32::from(self.start + self.end);
+        if self.end < self.start {
+            middle += 1440;
+        }
+        let d = (m * 2 + 60).abs_diff(middle) % 2880;
+        d.min(2880 - d) / 2
+    }
+}
+
+impl Default for WorkingHours {
+    /// 09:00-17:00
+    fn default() -> WorkingHours {
+        WorkingHours {
+            start: 9 * 60,
+            end: 17 * 60,
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// MeetingSlot struct
+
+/**
+One hour meeting slot with the local time in each timezone and whether it is within the working
+hours there
+
+```
+use dtg_lib::{tz, Date, DateTime, MeetingSlot, WorkingHours};
+
+let zones = [
+    (tz("UTC").unwrap(), WorkingHours::default()),
+    (tz("America/New_York").unwrap(), WorkingHours::default()),
+    (tz("Asia/Kolkata").unwrap(), WorkingHours::new("10-19").unwrap()),
+];
+let slots = MeetingSlot::list(Date::constant(2022, 7, 22), &zones).unwrap();
+
+assert_eq!(slots.len(), 24);
+assert_eq!(slots[13].dtg.rfc_3339(), "2022-07-22T13:00:00Z");
+assert_eq!(slots[13].local[1], DateTime::constant(2022, 7, 22, 9, 0, 0, 0));
+assert_eq!(slots[13].local[2].to_string(), "2022-07-22T18:30:00");
+assert_eq!(slots[13].working, [true, true, false]);
+
+let best = MeetingSlot::rank(&slots);
+
+assert_eq!(best[0].dtg.rfc_3339(), "2022-07-22T12:00:00Z");
+assert_eq!(best[0].working, [true, false, true]);
+assert_eq!(best[1].dtg.rfc_3339(), "2022-07-22T13:00:00Z");
+assert_eq!(best[1].working_count(), 2);
+```
+*/
+#[derive(Debug)]
+pub struct MeetingSlot {
+    pub dtg: Dtg,
+    pub local: Vec<DateTime>,
+    pub working: Vec<bool>,
+    distance: u32,
+}
+
+impl MeetingSlot {
+    /**
+    Get each hour of a date in the first timezone with the local times and working hours of every
+    timezone
+
+    # Errors
+
+    Returns an error if the date is out of range in the first timezone
+    */
+    pub fn list(
+        date: Date,
+        zones: &[(TimeZone, WorkingHours)],
+    ) -> Result<Vec<MeetingSlot>, DtgError> {
+        let Some((first, _)) = zones.first() else {
+            return Ok(vec![]);
+        };
+        let e = |e| {
+            DtgError::from(DtgErrorKind::InvalidTimestamp {
+                input: date.to_string(),
+            })
+            .with_source(e)
+        };
+        let start = date.to_zoned(first.clone()).map_err(e)?.timestamp();
+        let end = date
+            .tomorrow()
+            .and_then(|x| x.to_zoned(first.clone()))
+            .map_or(Timestamp::MAX, |x| x.timestamp());
+        let mut r = vec![];
+        let mut dt = start;
+        while dt < end {
+            let local = zones
+                .iter()
+                .map(|(tz, _)| tz.to_datetime(dt))
+                .collect::<Vec<_>>();
+            let working = zones
+                .iter()
+                .zip(&local)
+                .map(|((_, hours), local)| hours.contains(local.time()))
+                .collect();
+            let distance = zones
+                .iter()
+                .zip(&local)
+                .map(|((_, hours), local)| hours.distance(local.time()))
+                .sum();
+            r.push(MeetingSlot {
+                dtg: Dtg { dt },
+                local,
+                working,
+                distance,
+            });
+            let Ok(next) = dt.checked_add(SignedDuration::from_hours(1)) else {
+                break;
+            };
+            dt = next;
+        }
+        Ok(r)
+    }
+
+    /**
+    Number of timezones where the slot is within the working hours
+    */
+    #[must_use]
+    pub fn working_count(&self) -> usize {
+        self.working.iter().filter(|x| **x).count()
+    }
+
+    /**
+    Rank the slots within the working hours of at least one timezone by the number of timezones,
+    then by how close they are to the middle of each timezone's working hours, then by time
+    */
+    #[must_use]
+    pub fn rank(slots: &[MeetingSlot]) -> Vec<&MeetingSlot> {
+        let mut r = slots
+            .iter()
+            .filter(|x| x.working_count() > 0)
+            .collect::<Vec<_>>();
+        r.sort_by_key(|x| (std::cmp::Reverse(x.working_count()), x.distance, x.dtg.dt));
+        r
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Serde
+
+/**
+Serde support (`serde` feature)
+
+[Dtg] serializes as an RFC 3339 string with fractional seconds, [Duration] as an "x" format
+duration, and [Format] as its string (see [`Format`'s `FromStr`](#impl-FromStr-for-Format));
+use these modules with `#[serde(with = "...")]` to select another [Dtg] representation
+
+Module                                  | Representation
+----------------------------------------|-------------------------------------------------------
+[`rfc_3339`](serde::rfc_3339)           | RFC 3339 string with fractional seconds (default)
+[`epoch_seconds`](serde::epoch_seconds) | Integer epoch seconds (fractional seconds truncated)
+[`epoch_millis`](serde::epoch_millis)   | Integer epoch milliseconds (sub-millisecond truncated)
+[`x`](serde::x)                         | Extended "x" format string in UTC
+
+```
+use dtg_lib::{Dtg, Duration, Format};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+struct Event {
+    at: Dtg,
+    #[serde(with = "dtg_lib::serde::epoch_seconds")]
+    seconds: Dtg,
+    #[serde(with = "dtg_lib::serde::epoch_millis")]
+    millis: Dtg,
+    #[serde(with = "dtg_lib::serde::x")]
+    x: Dtg,
+    length: Duration,
+    format: Format,
+}
+
+let dtg = || Dtg::from("1658448142.5").unwrap();
+let json = concat!(
+    r#"{"at":"2022-07-22T00:02:22.5Z","seconds":1658448142,"millis":1658448142500,"#,
+    r#""x":"Xg6L02M.U","length":"1U0","format":"dtg"}"#,
+);
+
+let event = Event {
+    at: dtg(),
+    seconds: dtg(),
+    millis: dtg(),
+    x: dtg(),
+    length: Duration::from_x("1U0").unwrap(),
+    format: Format::Military,
+};
+assert_eq!(serde_json::to_string(&event).unwrap(), json);
+
+let event: Event = serde_json::from_str(json).unwrap();
+assert_eq!(event.at, dtg());
+assert_eq!(event.seconds, Dtg::from("1658448142").unwrap());
+assert_eq!(event.millis, dtg());
+assert_eq!(event.x, dtg());
+assert_eq!(event.length.to_x(), "1U0");
+assert_eq!(dtg().format(&Some(event.format), &None), "220002Z JUL 22");
+```
+*/
+#[cfg(feature = "serde")]
+pub mod serde {
+    use {
+        crate::{Dtg, Timestamp},
+        ::serde::{Deserialize, Deserializer, Serializer, de::Error},
+    };
+
+    /**
+    Serialize / deserialize a [Dtg] as an RFC 3339 string with fractional seconds
+
+    Deserializing accepts any offset, e.g. `2022-07-21T18:02:22-06:00`
+    */
+    pub mod rfc_3339 {
+        use super::{Deserialize, Deserializer, Dtg, Error, Serializer, Timestamp};
+
+        /**
+        Serialize a [Dtg] as an RFC 3339 string
+
+        # Errors
+
+        Returns the serializer's error
+        */
+        pub fn serialize<S: Serializer>(dtg: &Dtg, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(&dtg.dt)
+        }
+
+        /**
+        Deserialize a [Dtg] from an RFC 3339 string
+
+        # Errors
+
+        Returns an error if not an RFC 3339 string
+        */
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Dtg, D::Error> {
+            let s = String::deserialize(deserializer)?;
+            s.parse::<Timestamp>()
+                .map(|dt| Dtg { dt })
+                .map_err(|e| Error::custom(format!("Invalid timestamp: `{s}`: {e}")))
+        }
+    }
+
+    /**
+    Serialize / deserialize a [Dtg] as integer epoch seconds (fractional seconds are truncated)
+    */
+    pub mod epoch_seconds {
+        use super::{Deserialize, Deserializer, Dtg, Error, Serializer, Timestamp};
+
+        /**
+        Serialize a [Dtg] as integer epoch seconds
+
+        # Errors
+
+        Returns the serializer's error
+        */
+        pub fn serialize<S: Serializer>(dtg: &Dtg, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_i64(dtg.dt.as_second())
+        }
+
+        /**
+        Deserialize a [Dtg] from integer epoch seconds
+
+        # Errors
+
+        Returns an error if not an integer or out of range
+        */
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Dtg, D::Error> {
+            let n = i64::deserialize(deserializer)?;
+            Timestamp::from_second(n)
+                .map(|dt| Dtg { dt })
+                .map_err(|e| Error::custom(format!("Invalid timestamp: `{n}`: {e}")))
+        }
+    }
+
+    /**
+    Serialize / deserialize a [Dtg] as integer epoch milliseconds (sub-millisecond precision is
+    truncated)
+    */
+    pub mod epoch_millis {
+        use super::{Deserialize, Deserializer, Dtg, Error, Serializer, Timestamp};
+
+        /**
+        Serialize a [Dtg] as integer epoch milliseconds
+
+        # Errors
+
+        Returns the serializer's error
+        */
+        pub fn serialize<S: Serializer>(dtg: &Dtg, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_i64(dtg.dt.as_millisecond())
+        }
+
+        /**
+        Deserialize a [Dtg] from integer epoch milliseconds
+
+        # Errors
+
+        Returns an error if not an integer or out of range
+        */
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Dtg, D::Error> {
+            let n = i64::deserialize(deserializer)?;
+            Timestamp::from_millisecond(n)
+                .map(|dt| Dtg { dt })
+                .map_err(|e| Error::custom(format!("Invalid timestamp: `{n}`: {e}")))
+        }
+    }
+
+    /**
+    Serialize / deserialize a [Dtg] as an extended "x" format string in UTC
+
+    Whole seconds serialize as plain "x" format; deserializing accepts either
+    */
+    pub mod x {
+        use super::{Deserialize, Deserializer, Dtg, Error, Serializer};
+
+        /**
+        Serialize a [Dtg] as an extended "x" format string in UTC
+
+        # Errors
+
+        Returns the serializer's error
+        */
+        pub fn serialize<S: Serializer>(dtg: &Dtg, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&dtg.x_extended_format(&None))
+        }
+
+        /**
+        Deserialize a [Dtg] from an "x" or extended "x" format string
+
+        # Errors
+
+        Returns an error if not an "x" format string
+        */
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Dtg, D::Error> {
+            Dtg::from_x(&String::deserialize(deserializer)?).map_err(Error::custom)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for Dtg {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::rfc_3339::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for Dtg {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Dtg, D::Error> {
+        serde::rfc_3339::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for Duration {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_x())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for Duration {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let s = <String as ::serde::Deserialize>::deserialize(deserializer)?;
+        Duration::from_x(&s).map_err(::serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for Format {
+    /**
+    Serialize as a built-in name or custom format (see
+    [`Format`'s `Display`](#impl-Display-for-Format))
+    */
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for Format {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Format, D::Error> {
+        let s = <String as ::serde::Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(::serde::de::Error::custom)
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+
+/**
+Encode nanoseconds as extended "x" format fraction units (`1 / radix^digits` seconds)
+*/
+fn x_fraction_units(nanosecond: u32, alphabet: &XAlphabet) -> u128 {
+    (u128::from(nanosecond) * alphabet.fraction_scale()).div_ceil(1_000_000_000)
+}
+
+/**
+Decode extended "x" format fractional seconds to nanoseconds
+
+Returns `None` if not canonical (empty, too many digits, trailing zero digits, or not the encoding
+of a whole number of nanoseconds), or the position of an invalid char
+*/
+fn x_fraction(fraction: &str, alphabet: &XAlphabet) -> Option<Result<i32, usize>> {
+    let chars = fraction.chars().collect::<Vec<char>>();
+    let digits = alphabet.fraction_digits();
+    if chars.is_empty()
+        || chars.len() > digits
+        || alphabet.decode(&chars[chars.len() - 1..]) == Some(0)
+    {
+        return None;
+    }
+    let mut units = 0;
+    for i in 0..digits {
+        let digit = match chars.get(i) {
+            Some(c) => match alphabet.decode(&[*c]) {
+                Some(digit) => digit,
+                None => return Some(Err(i)),
+            },
+            None => 0,
+        };
+        units = units * u128::from(alphabet.radix()) + digit;
+    }
+    let nanosecond = u32::try_from(units * 1_000_000_000 / alphabet.fraction_scale()).ok()?;
+    if x_fraction_units(nanosecond, alphabet) != units {
+        return None;
+    }
+    i32::try_from(nanosecond).ok().map(Ok)
+}
+
+/**
+Decode an extended "x" format offset (`+`/`-` hours, minutes, and optional seconds) to seconds
+
+Returns `None` if not canonical (missing minutes, zero seconds, or zero offset), or the position of
+the first char of an invalid field
+*/
+fn x_offset(offset: &str, alphabet: &XAlphabet) -> Option<Result<i32, usize>> {
+    let chars = offset.chars().skip(1).collect::<Vec<char>>();
+    let (h, m, s) = (alphabet.width(25), alphabet.width(59), alphabet.width(59));
+    if chars.len() != h + m && chars.len() != h + m + s {
+        return None;
+    }
+    let mut hms = [0; 3];
+    for (i, range) in [0..h, h..h + m, h + m..h + m + s].into_iter().enumerate() {
+        if let Some(field) = chars.get(range.clone()) {
+            let Some(n) = alphabet.decode(field).and_then(|n| i32::try_from(n).ok()) else {
+                return Some(Err(1 + range.start));
+            };
+            hms[i] = n;
+        }
+    }
+    let [hours, minutes, seconds] = hms;
+    if minutes > 59
+        || seconds > 59
+        || (chars.len() > h + m && seconds == 0)
+        || hours + minutes + seconds == 0
+    {
+        return None;
+    }
+    let seconds = hours * 3600 + minutes * 60 + seconds;
+    Some(Ok(if offset.starts_with('-') {
+        -seconds
+    } else {
+        seconds
+    }))
+}
+
+/**
+Use a timezone database for [tz] and the other timezone functions
+
+* `system`: The system database (in the `TZDIR` environment variable's directory, or
+  `/usr/share/zoneinfo`, etc.)
+* `bundled`: The database bundled with the `tzdb-bundle` feature
+* Any other value: A zoneinfo directory, or a concatenated (Android) `tzdata` file
+
+The initial database is from the `DTG_TZDB` environment variable, if set, otherwise `system`; if
+`DTG_TZDB` is invalid, [tz] returns an error until a database is set. Timezone abbreviations
+(see [`tz_abbreviations`]) are indexed from the database in use when first needed.
+
+```
+use dtg_lib::{tz, tz_db_set, DtgErrorKind};
+
+tz_db_set("system").unwrap();
+
+assert!(tz("America/New_York").is_ok());
+
+assert_eq!(
+    tz_db_set("nonexistent").unwrap_err().kind(),
+    &DtgErrorKind::InvalidTimezoneDatabase { database: "nonexistent".into() },
+);
+```
+
+# Errors
+
+Returns an error if not able to open the timezone database
+*/
+pub fn tz_db_set(source: &str) -> Result<(), DtgError> {
+    let (db, version) = tz_db_open(source)?;
+    *TZ_DB
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = TzDb {
+        db,
+        version,
+        invalid: None,
+    };
+    *TZ_ABBREVIATIONS
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = None;
+    Ok(())
+}
+
+/**
+Define a timezone alias (case-insensitive) for one or more comma-separated timezones
+
+Aliases can refer to other aliases; [tz] resolves aliases for one timezone and [`tz_split`]
+expands aliases for any number.
+
+```
+use dtg_lib::{tz, tz_alias_set, tz_split};
+
+tz_alias_set("office", "America/Chicago").unwrap();
+tz_alias_set("team", "UTC,office,Asia/Kolkata").unwrap();
+
+assert_eq!(tz("Office"), tz("America/Chicago"));
+assert_eq!(tz_split("team,hq"), ["UTC", "America/Chicago", "Asia/Kolkata", "hq"]);
+assert!(tz_alias_set("a,b", "UTC").is_err());
+```
+
+# Errors
+
+Returns an error if the alias is empty or contains a `,`, or the timezones are empty
+*/
+pub fn tz_alias_set(alias: &str, zones: &str) -> Result<(), DtgError> {
+    if alias.is_empty() || alias.contains(',') || zones.is_empty() {
+        return Err(DtgErrorKind::InvalidTimezoneAlias {
+            alias: alias.to_string(),
+            zones: zones.to_string(),
+        }
+        .into());
+    }
+    TZ_ALIASES
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .insert(alias.to_lowercase(), zones.to_string());
+    Ok(())
+}
+
+/**
+Split a comma-separated list of timezones, expanding aliases (see [`tz_alias_set`]) and keeping
+POSIX TZ string rules (`,M3.2.0`, `,J60`, `,59`) with their timezone
+
+```
+use dtg_lib::tz_split;
+
+assert_eq!(
+    tz_split("UTC,EST5EDT,M3.2.0,M11.1.0,J"),
+    ["UTC", "EST5EDT,M3.2.0,M11.1.0", "J"],
+);
+```
+*/
+#[must_use]
+pub fn tz_split(s: &str) -> Vec<String> {
+    tz_split_depth(s, 0)
+}
+
+/**
+Get the version of the timezone database in use (see [`tz_db_set`]), if known
+
+```
+use dtg_lib::tz_db_version;
+
+if let Some(version) = tz_db_version() {
+    assert!(version.starts_with("20"));
+}
+```
+*/
+#[must_use]
+pub fn tz_db_version() -> Option<String> {
+    TZ_DB
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .version
+        .clone()
+}
+
+/**
+Get the names of the IANA and military timezones that [tz] accepts
 
-Spec.  | Example       | Description
--------|---------------|----------------------------------------------------------------------
-`%H`   | `00`          | Hour number (00--23), zero-padded to 2 digits.
-`%k`   | ` 0`          | Same as `%H` but space-padded. Same as `%_H`.
-`%I`   | `12`          | Hour number in 12-hour clocks (01--12), zero-padded to 2 digits.
-`%l`   | `12`          | Same as `%I` but space-padded. Same as `%_I`.
-`%P`   | `am`          | `am` or `pm` in 12-hour clocks.
-`%p`   | `AM`          | `AM` or `PM` in 12-hour clocks.
-`%M`   | `34`          | Minute number (00--59), zero-padded to 2 digits.
-`%S`   | `60`          | Second number (00--60), zero-padded to 2 digits.
-`%f`   | `026490000`   | The fractional seconds (in nanoseconds) since last whole second.
-`%.f`  | `.026490`     | Similar to `.%f` but left-aligned. These all consume the leading dot.
-`%.3f` | `.026`        | Similar to `.%f` but left-aligned but fixed to a length of 3.
-`%.6f` | `.026490`     | Similar to `.%f` but left-aligned but fixed to a length of 6.
-`%.9f` | `.026490000`  | Similar to `.%f` but left-aligned but fixed to a length of 9.
-`%3f`  | `026`         | Similar to `%.3f` but without the leading dot.
-`%6f`  | `026490`      | Similar to `%.6f` but without the leading dot.
-`%9f`  | `026490000`   | Similar to `%.9f` but without the leading dot.
-`%R`   | `00:34`       | Hour-minute format. Same as `%H:%M`.
-`%T`   | `00:34:60`    | Hour-minute-second format. Same as `%H:%M:%S`.
-`%X`   | `00:34:60`    | Locale's time representation (e.g., 23:13:48).
-`%r`   | `12:34:60 AM` | Hour-minute-second format in 12-hour clocks. Same as `%I:%M:%S %p`.
+```
+use dtg_lib::tz_names;
 
-## Time zone specifiers
+let names = tz_names();
 
-Spec. | Example  | Description
-------|----------|--------------------------------------------------------------------------
-`%Z`  | `ACST`   | Local time zone name. Skips all non-whitespace characters during parsing.
-`%z`  | `+0930`  | Offset from the local time to UTC (with UTC being `+0000`).
-`%:z` | `+09:30` | Same as `%z` but with a colon.
-`%#z` | `+09`    | *Parsing only:* Same as `%z` but allows minutes to be missing or present.
+assert!(names.contains(&String::from("America/New_York")));
+assert!(names.contains(&String::from("Romeo")));
+assert!(!names.iter().any(|x| x.starts_with("right/")));
+```
+*/
+#[must_use]
+pub fn tz_names() -> Vec<String> {
+    let mut names = tz_db()
+        .available()
+        .map(|x| x.to_string())
+        .filter(|x| !x.starts_with("right/") && !["Factory", "posixrules"].contains(&x.as_str()))
+        .chain(MILITARY_ZONES.iter().map(|(_, name, _)| name.to_string()))
+        .chain([String::from("Juliet")])
+        .collect::<Vec<String>>();
+    names.sort();
+    names.dedup();
+    names
+}
 
-## Date & time specifiers
+/**
+Search timezone names (see [`tz_names`]) ignoring case, accents, spaces, `_`s, and `-`s
 
-Spec. | Example                            | Description
-------|------------------------------------|------------------------------------------------------------------
-`%c`  | `Sun Jul  8 00:34:60 2001`         | Locale's date and time (e.g., Thu Mar  3 23:05:25 2005).
-`%+`  | `2001-07-08T00:34:60.026490+09:30` | ISO 8601 / RFC 3339 date & time format.
-`%s`  | `994518299`                        | UNIX timestamp, the number of seconds since 1970-01-01 00:00 UTC.
+Results are ranked by exact name, exact city (last component), component prefix, then any match,
+and then sorted by name.
 
-## Special specifiers
+```
+use dtg_lib::tz_search;
 
-Spec. | Description
-------|------------------------
-`%t`  | Literal tab (`\t`).
-`%n`  | Literal newline (`\n`).
-`%%`  | Literal percent sign.
+assert_eq!(tz_search("newyork"), ["America/New_York"]);
+assert_eq!(tz_search("São Paulo"), ["America/Sao_Paulo"]);
+assert_eq!(tz_search("kolkata"), ["Asia/Kolkata"]);
+assert_eq!(tz_search("port au"), ["America/Port-au-Prince"]);
+assert_eq!(tz_search("ndian")[0], "America/Indiana/Indianapolis");
+assert!(tz_search("xyzzy").is_empty());
+```
 */
-#[derive(Clone)]
-pub enum Format {
-    A,
-    BCD,
-    X,
-    Custom(String),
+#[must_use]
+pub fn tz_search(search: &str) -> Vec<String> {
+    tz_ranked(search)
+        .into_iter()
+        .map(|(_, name)| name)
+        .collect()
 }
 
-impl Format {
-    /**
-    Create a default [Format]
-    */
-    #[must_use]
-    pub fn new() -> Format {
-        Format::Custom(DEFAULT.to_string())
-    }
+/**
+Get the timezone names for an ISO 3166 2-character country code in tzdb `zone.tab` order
 
-    /**
-    Create an RFC 3339 [Format]
-    */
-    #[must_use]
-    pub fn rfc_3339() -> Format {
-        Format::Custom(RFC_3339.to_string())
-    }
+```
+use dtg_lib::tz_country;
 
-    /**
-    Create a custom [Format]
-    */
-    #[must_use]
-    pub fn custom(s: &str) -> Format {
-        Format::Custom(s.to_string())
-    }
+let br = tz_country("br");
 
-    /**
-    Format a [Timestamp] with a timezone
-    */
-    fn with(&self, dt: &Timestamp, tz: &TimeZone) -> String {
-        match self {
-            Format::Custom(f) => {
-                if f == "%s" {
-                    format!("{}", dt.as_second())
-                } else if f == EPOCH {
-                    format!("{}.{}", dt.as_second(), dt.subsec_nanosecond())
-                } else if f == RFC_3339 {
-                    dt.strftime(f).to_string()
-                } else {
-                    let mut f = f.clone();
-                    if f.contains("%n") {
-                        f = f.replace("%n", "\n");
-                    }
-                    if f.contains("%f") {
-                        f = f.replace("%f", &format!("{:09}", dt.subsec_nanosecond()));
-                    }
-                    dt.to_zoned(tz.clone()).strftime(&f).to_string()
-                }
-            }
-            Format::A => {
-                let epoch_seconds = i128::from(dt.as_second());
-                let epoch_nanoseconds = dt.as_nanosecond() - epoch_seconds * 1_000_000_000;
-                let epoch = format!("{epoch_seconds}.{epoch_nanoseconds:09}");
-                let rfc = dt.strftime(RFC_3339).to_string();
-                let default = dt.to_zoned(TimeZone::UTC).strftime(DEFAULT).to_string();
-                let zoned_default = dt.to_zoned(tz.clone()).strftime(DEFAULT).to_string();
-                [epoch, rfc, default, zoned_default].join("\n")
-            }
-            Format::X => Format::x(dt),
-            Format::BCD => Format::bcd(dt, tz),
-        }
-    }
+assert!(br.contains(&String::from("America/Sao_Paulo")));
+assert!(br.contains(&String::from("America/Manaus")));
+assert_eq!(tz_country("CH"), ["Europe/Zurich"]);
+assert_eq!(tz_country("NO"), ["Europe/Oslo"]);
+assert!(tz_country("XX").is_empty());
+```
+*/
+#[must_use]
+pub fn tz_country(code: &str) -> Vec<String> {
+    ZONE_TAB
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut columns = line.split('\t');
+            let country = columns.next()?;
+            let zone = columns.nth(1)?;
+            country.eq_ignore_ascii_case(code).then(|| zone.to_string())
+        })
+        .collect()
+}
 
-    /**
-    Format a [Timestamp] with "x" format
-    */
-    fn x(dt: &Timestamp) -> String {
-        let dt = dt.to_zoned(TimeZone::UTC);
-        let mut year = dt.year();
-        let mut y = vec![];
-        if year == 0 {
-            y.push(0);
-        }
-        while year > 0 {
-            y.push((year % 60) as i8);
-            year /= 60;
-        }
-        let year = y
-            .iter()
-            .rev()
-            .map(|x| ITOC.get(x).unwrap())
-            .collect::<String>();
-        let mon = ITOC.get(&(dt.month() - 1)).unwrap();
-        let day = ITOC.get(&(dt.day() - 1)).unwrap();
-        let h = ITOC.get(&(dt.hour())).unwrap();
-        let m = ITOC.get(&(dt.minute())).unwrap();
-        let s = ITOC.get(&(dt.second())).unwrap();
-        format!("{year}{mon}{day}{h}{m}{s}")
-    }
+/**
+Get the candidate zones for a timezone abbreviation in use from 2020 to 2030
 
-    /**
-    Format a [Timestamp] like a binary clock using the Braille Patterns Unicode Block and `|`
-    separators
-    */
-    fn bcd(dt: &Timestamp, tz: &TimeZone) -> String {
-        let dt = dt.to_zoned(tz.clone());
-        let yyyy = dt.year();
-        #[allow(clippy::cast_sign_loss)]
-        let (mut r, yyyy) = if yyyy < 0 {
-            (String::from("-"), (-yyyy) as u32)
-        } else {
-            (String::new(), yyyy as u32)
-        };
-        let cc = u8::try_from(yyyy / 100).unwrap();
-        let yy = u8::try_from(yyyy - yyyy / 100 * 100).unwrap();
-        #[allow(clippy::cast_sign_loss)]
-        for (i, n) in [
-            cc,
-            yy,
-            dt.month() as u8,
-            dt.day() as u8,
-            dt.hour() as u8,
-            dt.minute() as u8,
-            dt.second() as u8,
-        ]
+Candidates are sorted with the preferred zone (if the abbreviation is ambiguous) first, followed by
+the others with the same offset, then by offset and zone.
+
+```
+use dtg_lib::{tz_abbreviations, Offset};
+
+let ist = tz_abbreviations("IST");
+
+assert_eq!(ist[0].zone, "Asia/Kolkata");
+assert_eq!(ist[0].offset, Offset::from_seconds(19_800).unwrap());
+assert!(ist.iter().any(|x| x.zone == "Europe/Dublin"));
+
+for (abbreviation, zone) in [
+    ("BST", "Europe/London"),
+    ("EST", "America/New_York"),
+    ("MST", "America/Denver"),
+    ("AEST", "Australia/Sydney"),
+] {
+    assert_eq!(tz_abbreviations(abbreviation)[0].zone, zone);
+}
+
+assert!(tz_abbreviations("XYZ").is_empty());
+```
+*/
+#[must_use]
+pub fn tz_abbreviations(abbreviation: &str) -> Vec<TzAbbreviation> {
+    let abbreviation = abbreviation.to_uppercase();
+    let mut r = TZ_ABBREVIATIONS
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .get_or_insert_with(tz_abbreviation_index)
+        .get(&abbreviation)
+        .cloned()
+        .unwrap_or_default();
+    let preferred = TZ_ABBREVIATION_PREFERENCES
         .iter()
-        .enumerate()
-        {
-            if i >= 2 {
-                r.push('|');
-            }
-            r.push(bbd_lib::encode_bcd(*n));
-        }
-        r
-    }
+        .find(|(x, _)| *x == abbreviation)
+        .and_then(|(_, zone)| r.iter().find(|x| x.zone == *zone))
+        .cloned();
+    r.sort_by_key(|x| {
+        (
+            preferred
+                .as_ref()
+                .map(|p| (x.offset != p.offset, x.zone != p.zone)),
+            x.offset,
+            x.zone.clone(),
+        )
+    });
+    r
 }
 
-impl Default for Format {
-    fn default() -> Format {
-        Format::new()
+/**
+Get a fixed offset timezone for a timezone abbreviation in use from 2020 to 2030
+
+An ambiguous abbreviation (with candidate zones at different offsets) uses its preferred zone's
+offset if it has one.
+
+```
+use dtg_lib::{tz_abbreviation, Dtg};
+
+let dtg = Dtg::from("1658448142").unwrap();
+
+assert_eq!(
+    dtg.default(&tz_abbreviation("IST").ok()),
+    "Fri 22 Jul 2022 05:32:22 IST",
+);
+assert_eq!(
+    dtg.default(&tz_abbreviation("CST").ok()),
+    "Thu 21 Jul 2022 18:02:22 CST",
+);
+```
+
+# Errors
+
+Returns an error if the abbreviation is unknown, or ambiguous without a preferred zone
+*/
+pub fn tz_abbreviation(abbreviation: &str) -> Result<TimeZone, DtgError> {
+    let invalid = || {
+        DtgError::from(DtgErrorKind::InvalidTimezone {
+            input: abbreviation.to_string(),
+        })
+    };
+    let mut candidates = tz_abbreviations(abbreviation);
+    let Some(first) = candidates.first().cloned() else {
+        return Err(invalid());
+    };
+    let preferred = TZ_ABBREVIATION_PREFERENCES
+        .iter()
+        .any(|(x, zone)| x.eq_ignore_ascii_case(abbreviation) && first.zone == *zone);
+    candidates.dedup_by_key(|x| x.offset);
+    if candidates.len() > 1 && !preferred {
+        return Err(DtgErrorKind::AmbiguousAbbreviation {
+            abbreviation: abbreviation.to_string(),
+            candidates: candidates.iter().map(ToString::to_string).collect(),
+        }
+        .into());
     }
-}
 
-//--------------------------------------------------------------------------------------------------
-// Functions
+    // POSIX TZ string offsets are west of UTC
+    let seconds = -first.offset.seconds();
+    let (sign, seconds) = (if seconds < 0 { '-' } else { '+' }, seconds.abs());
+    TimeZone::posix(&format!(
+        "<{}>{sign}{}:{:02}:{:02}",
+        abbreviation.to_uppercase(),
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+    ))
+    .map_err(|_| invalid())
+}
 
 /**
 Get a timezone by name
 
 ```
-use dtg_lib::{tz, DtgError, TimeZone};
+use dtg_lib::{tz, Dtg, DtgErrorKind, Offset, TimeZone};
 
 assert_eq!(tz("UTC"), Ok(TimeZone::UTC));
 
-assert_eq!(tz("nonexistent"), Err(DtgError::new("Invalid timezone: `nonexistent`", 102)));
+// Military time zone letters and names
+let romeo = TimeZone::fixed(Offset::from_hours(-5).unwrap());
+
+assert_eq!(tz("R"), Ok(romeo.clone()));
+assert_eq!(tz("romeo"), Ok(romeo));
+assert_eq!(tz("Z"), Ok(TimeZone::UTC));
+
+// Fixed UTC offsets
+let ist = TimeZone::fixed(Offset::from_seconds(19_800).unwrap());
+
+assert_eq!(tz("+05:30"), Ok(ist.clone()));
+assert_eq!(tz("UTC+0530"), Ok(ist));
+assert_eq!(tz("GMT-4"), Ok(TimeZone::fixed(Offset::from_hours(-4).unwrap())));
+
+// Timezone abbreviations rather than legacy zones with DST rules
+let summer = Dtg::from("1658448142").unwrap();
+
+assert_eq!(summer.default(&tz("CET").ok()), "Fri 22 Jul 2022 01:02:22 CET");
+
+// POSIX TZ strings
+assert_eq!(
+    tz("EST5EDT,M3.2.0,M11.1.0"),
+    Ok(TimeZone::posix("EST5EDT,M3.2.0,M11.1.0").unwrap()),
+);
+
+assert_eq!(
+    tz("nonexistent").unwrap_err().kind(),
+    &DtgErrorKind::InvalidTimezone { input: "nonexistent".into() },
+);
 ```
 
+Aliases (see [`tz_alias_set`]) for one timezone are resolved first. A path to a `TZif` file is also
+accepted if the name is not in the timezone database (see
+[`tz_db_set`]).
+
+Fixed UTC offsets are `[UTC|GMT](+|-)H[H][[:]MM[[:]SS]]`, where, unlike POSIX TZ strings and
+`Etc/GMT` zones, `UTC+3` is east of UTC. Timezone abbreviations (see [`tz_abbreviation`]) take
+precedence over the legacy zones of the timezone database with DST rules, so `CET` is always +01.
+Any other name that is not in the timezone database is parsed as a POSIX TZ string like
+`EST5EDT,M3.2.0,M11.1.0`, or a search (see [`tz_search`]) that matches a name or city exactly, like
+`new york`.
+
+Military time zones: `Z` / `Zulu` (UTC), `A` / `Alpha` (+1) ... `I` / `India` (+9), `K` / `Kilo`
+(+10) ... `M` / `Mike` (+12), `N` / `November` (-1) ... `Y` / `Yankee` (-12), and `J` / `Juliet`
+(local); letters and names are case-insensitive.
+
 Timezones:
 
 ```text
@@ -1350,14 +4577,338 @@ localtime
 Returns an error if not able to parse the given `&str` as a timezone
 */
 pub fn tz(s: &str) -> Result<TimeZone, DtgError> {
+    if let Some(database) = &TZ_DB
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .invalid
+    {
+        return Err(DtgErrorKind::InvalidTimezoneDatabase {
+            database: database.clone(),
+        }
+        .into());
+    }
+    if TZ_ALIASES
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .contains_key(&s.to_lowercase())
+    {
+        return match tz_split(s).as_slice() {
+            [zone] => tz_unaliased(zone),
+            _ => Err(DtgErrorKind::InvalidTimezone {
+                input: s.to_string(),
+            }
+            .into()),
+        };
+    }
+    tz_unaliased(s)
+}
+
+/// Whether a string looks like a timezone abbreviation with candidate zones
+fn tz_is_abbreviation(s: &str) -> bool {
+    (2..=6).contains(&s.len())
+        && s.bytes().all(|b| b.is_ascii_alphabetic())
+        && !tz_abbreviations(s).is_empty()
+}
+
+/**
+Get a timezone by name without resolving aliases
+*/
+fn tz_unaliased(s: &str) -> Result<TimeZone, DtgError> {
+    if let Some((_, _, hours)) = MILITARY_ZONES.iter().find(|(letter, name, _)| {
+        s.eq_ignore_ascii_case(name) || s.eq_ignore_ascii_case(letter.encode_utf8(&mut [0; 4]))
+    }) {
+        return Ok(if *hours == 0 {
+            TimeZone::UTC
+        } else {
+            TimeZone::fixed(Offset::constant(*hours))
+        });
+    }
+    if s.eq_ignore_ascii_case("J") || s.eq_ignore_ascii_case("Juliet") {
+        return tz("local");
+    }
     match s {
         "local" => match iana_time_zone::get_timezone() {
             Ok(local) => tz(&local),
-            Err(_) => Err(DtgError::new("Failed to get local timezone", 103)),
+            Err(_) => Err(DtgErrorKind::LocalTimezone.into()),
         },
-        _ => match jiff::tz::db().get(s) {
+        _ => match tz_db().get(s) {
+            // Abbreviations before legacy tzdb zones (`CET`, `MST`, ...) unless fixed (`UTC`)
+            Ok(z) if z.to_fixed_offset().is_err() && tz_is_abbreviation(s) => tz_abbreviation(s),
             Ok(z) => Ok(z),
-            Err(_) => Err(DtgError::new(&format!("Invalid timezone: `{s}`"), 102)),
+            Err(_) if Path::new(s).is_file() => std::fs::read(s)
+                .ok()
+                .and_then(|data| TimeZone::tzif(s, &data).ok())
+                .ok_or_else(|| {
+                    DtgErrorKind::InvalidTzif {
+                        path: s.to_string(),
+                    }
+                    .into()
+                }),
+            Err(_) => match tz_offset(s) {
+                Some(offset) => Ok(TimeZone::fixed(offset)),
+                None if tz_is_abbreviation(s) => tz_abbreviation(s),
+                None => TimeZone::posix(s)
+                    .or_else(|_| match tz_ranked(s).first() {
+                        Some((rank, name)) if *rank <= 1 => tz_db().get(name),
+                        _ => TimeZone::posix(s),
+                    })
+                    .map_err(|_| {
+                        DtgError::from(DtgErrorKind::InvalidTimezone {
+                            input: s.to_string(),
+                        })
+                    }),
+            },
         },
     }
 }
+
+/**
+Split a comma-separated list of timezones, expanding aliases up to 8 levels deep
+*/
+fn tz_split_depth(s: &str, depth: usize) -> Vec<String> {
+    let mut zones: Vec<String> = vec![];
+    for i in s.split(',') {
+        let rule = i.starts_with(|c: char| c.is_ascii_digit())
+            || (i.starts_with(['M', 'J']) && i[1..].starts_with(|c: char| c.is_ascii_digit()));
+        match zones.last_mut() {
+            Some(last) if rule => {
+                last.push(',');
+                last.push_str(i);
+            }
+            _ => zones.push(i.to_string()),
+        }
+    }
+    let aliases = TZ_ALIASES
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone();
+    zones
+        .into_iter()
+        .flat_map(|zone| match aliases.get(&zone.to_lowercase()) {
+            Some(alias) if depth < 8 => tz_split_depth(alias, depth + 1),
+            _ => vec![zone],
+        })
+        .collect()
+}
+
+/**
+Build the timezone abbreviation index from the transitions within [`TZ_ABBREVIATION_WINDOW`]
+*/
+fn tz_abbreviation_index() -> HashMap<String, Vec<TzAbbreviation>> {
+    let (start, end) = TZ_ABBREVIATION_WINDOW;
+    let mut r: HashMap<String, Vec<TzAbbreviation>> = HashMap::new();
+    for zone in tz_names() {
+        if !zone.contains('/') || zone.starts_with("Etc/") {
+            continue;
+        }
+        let Ok(tz) = tz_db().get(&zone) else {
+            continue;
+        };
+        let info = tz.to_offset_info(start);
+        let mut changes = vec![(info.abbreviation().to_string(), info.offset(), info.dst())];
+        for t in tz.following(start).take_while(|t| t.timestamp() < end) {
+            changes.push((t.abbreviation().to_string(), t.offset(), t.dst()));
+        }
+        for (abbreviation, offset, dst) in changes {
+            if abbreviation.starts_with(['+', '-']) {
+                continue;
+            }
+            let candidate = TzAbbreviation {
+                zone: zone.clone(),
+                offset,
+                dst: dst.is_dst(),
+            };
+            let candidates = r.entry(abbreviation).or_default();
+            if !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+    }
+    r
+}
+
+/**
+Get the timezone database in use
+*/
+fn tz_db() -> TimeZoneDatabase {
+    TZ_DB
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .db
+        .clone()
+}
+
+/**
+Open a timezone database (see [`tz_db_set`]) and get its version
+*/
+fn tz_db_open(source: &str) -> Result<(TimeZoneDatabase, Option<String>), DtgError> {
+    let invalid = || {
+        DtgError::from(DtgErrorKind::InvalidTimezoneDatabase {
+            database: source.to_string(),
+        })
+    };
+    let (db, version) = match source {
+        "system" => {
+            let dir = std::env::var_os("TZDIR")
+                .map(std::path::PathBuf::from)
+                .into_iter()
+                .chain(["/usr/share/zoneinfo", "/etc/zoneinfo"].map(std::path::PathBuf::from))
+                .find(|x| x.is_dir());
+            (
+                TimeZoneDatabase::from_env(),
+                dir.and_then(|x| tz_db_dir_version(&x)),
+            )
+        }
+        "bundled" => {
+            #[cfg(feature = "tzdb-bundle")]
+            let version = jiff_tzdb::VERSION.map(String::from);
+            #[cfg(not(feature = "tzdb-bundle"))]
+            let version = None;
+            (TimeZoneDatabase::bundled(), version)
+        }
+        _ => {
+            let path = Path::new(source);
+            if path.is_dir() {
+                (
+                    TimeZoneDatabase::from_dir(path).map_err(|_| invalid())?,
+                    tz_db_dir_version(path),
+                )
+            } else {
+                let version = std::fs::read(path)
+                    .ok()
+                    .and_then(|data| data.get(..12).map(<[u8]>::to_vec))
+                    .and_then(|header| String::from_utf8(header).ok())
+                    .and_then(|header| {
+                        Some(
+                            header
+                                .strip_prefix("tzdata")?
+                                .trim_end_matches('\0')
+                                .to_string(),
+                        )
+                    });
+                (
+                    TimeZoneDatabase::from_concatenated_path(path).map_err(|_| invalid())?,
+                    version,
+                )
+            }
+        }
+    };
+    if db.is_definitively_empty() {
+        return Err(invalid());
+    }
+    Ok((db, version))
+}
+
+/**
+Get the version of a zoneinfo directory from its `+VERSION` or `tzdata.zi` file
+*/
+fn tz_db_dir_version(dir: &Path) -> Option<String> {
+    if let Ok(version) = std::fs::read_to_string(dir.join("+VERSION")) {
+        return Some(version.trim().to_string());
+    }
+    std::fs::read_to_string(dir.join("tzdata.zi"))
+        .ok()?
+        .lines()
+        .next()?
+        .strip_prefix("# version ")
+        .map(String::from)
+}
+
+/**
+Rank timezone names matching a search (0: exact name, 1: exact city, 2: component prefix, 3: other)
+*/
+fn tz_ranked(search: &str) -> Vec<(usize, String)> {
+    let search = tz_normalize(search);
+    if search.is_empty() {
+        return vec![];
+    }
+    let mut r = tz_names()
+        .into_iter()
+        .filter_map(|name| {
+            let normalized = tz_normalize(&name);
+            let components = normalized.split('/').collect::<Vec<&str>>();
+            let rank = if normalized == search {
+                0
+            } else if components.last() == Some(&search.as_str()) {
+                1
+            } else if components.iter().any(|x| x.starts_with(&search)) {
+                2
+            } else if normalized.contains(&search) {
+                3
+            } else {
+                return None;
+            };
+            Some((rank, name))
+        })
+        .collect::<Vec<(usize, String)>>();
+    r.sort();
+    r
+}
+
+/**
+Normalize a timezone name or search: lowercase without accents, spaces, `_`s, or `-`s
+*/
+fn tz_normalize(s: &str) -> String {
+    s.to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace() && !['_', '-'].contains(c))
+        .map(|c| match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+            'ç' => 'c',
+            'è' | 'é' | 'ê' | 'ë' => 'e',
+            'ì' | 'í' | 'î' | 'ï' => 'i',
+            'ñ' => 'n',
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => 'o',
+            'ù' | 'ú' | 'û' | 'ü' => 'u',
+            'ý' | 'ÿ' => 'y',
+            _ => c,
+        })
+        .collect()
+}
+
+/**
+Parse a fixed UTC offset (`[UTC|GMT](+|-)H[H][[:]MM[[:]SS]]`)
+
+Unlike POSIX TZ strings and `Etc/GMT` zones, `UTC+3` and `GMT-4` are east and west of UTC,
+respectively.
+*/
+fn tz_offset(s: &str) -> Option<Offset> {
+    let t = match s.get(..3) {
+        Some(prefix)
+            if prefix.eq_ignore_ascii_case("UTC") || prefix.eq_ignore_ascii_case("GMT") =>
+        {
+            &s[3..]
+        }
+        _ => s,
+    };
+    let (negative, t) = match t.strip_prefix('-') {
+        Some(t) => (true, t),
+        None => (false, t.strip_prefix('+')?),
+    };
+
+    // Hours are 1 or 2 digits, followed by 2 digit minutes and seconds with optional `:`s
+    let (hours, mut rest) = t.split_at(t.bytes().take(2).take_while(u8::is_ascii_digit).count());
+    let mut fields = vec![hours];
+    while !rest.is_empty() {
+        rest = rest.strip_prefix(':').unwrap_or(rest);
+        let field = rest.get(..2)?;
+        fields.push(field);
+        rest = &rest[2..];
+    }
+    if hours.is_empty()
+        || fields.len() > 3
+        || !fields.iter().all(|x| x.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return None;
+    }
+    let [hours, minutes, seconds] = [0, 1, 2].map(|i| {
+        fields
+            .get(i)
+            .map_or(0, |x| x.parse::<i32>().unwrap_or_default())
+    });
+    if minutes > 59 || seconds > 59 {
+        return None;
+    }
+    let seconds = hours * 3600 + minutes * 60 + seconds;
+    Offset::from_seconds(if negative { -seconds } else { seconds }).ok()
+}
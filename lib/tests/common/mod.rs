@@ -0,0 +1,18 @@
+/*!
+Shared Property Test Strategies
+*/
+
+// # Crates
+
+use {dtg_lib::Timestamp, proptest::prelude::*};
+
+// # Helper functions
+
+/// Any valid timestamp
+pub fn timestamp() -> impl Strategy<Value = Timestamp> {
+    (
+        Timestamp::MIN.as_second()..=Timestamp::MAX.as_second(),
+        0..1_000_000_000_i32,
+    )
+        .prop_filter_map("out of range", |(s, ns)| Timestamp::new(s, ns).ok())
+}
@@ -0,0 +1,133 @@
+/*!
+Serde Property Tests
+*/
+
+#![cfg(feature = "serde")]
+
+// # Crates
+
+mod common;
+
+use {
+    common::timestamp,
+    dtg_lib::{ClockGlyphs, ClockMode, Dtg, Duration, Format, Timestamp, XAlphabet},
+    proptest::prelude::*,
+    serde::{Deserialize, Serialize},
+};
+
+// # Helper functions
+
+/// All representations of a [Dtg]
+#[derive(Deserialize, Serialize)]
+struct Event {
+    rfc_3339: Dtg,
+    #[serde(with = "dtg_lib::serde::epoch_seconds")]
+    seconds: Dtg,
+    #[serde(with = "dtg_lib::serde::epoch_millis")]
+    millis: Dtg,
+    #[serde(with = "dtg_lib::serde::x")]
+    x: Dtg,
+}
+
+impl Event {
+    fn new(dt: &Timestamp) -> Event {
+        Event {
+            rfc_3339: Dtg::from_dt(dt),
+            seconds: Dtg::from_dt(dt),
+            millis: Dtg::from_dt(dt),
+            x: Dtg::from_dt(dt),
+        }
+    }
+}
+
+// # Tests
+
+proptest! {
+    #[test]
+    fn serde_dtg_round_trip(dt in timestamp()) {
+        let json = serde_json::to_string(&Event::new(&dt)).unwrap();
+        let event = serde_json::from_str::<Event>(&json).unwrap();
+        prop_assert_eq!(event.rfc_3339, Dtg::from_dt(&dt));
+        prop_assert_eq!(event.x, Dtg::from_dt(&dt));
+        prop_assert_eq!(event.seconds.timestamp().as_second(), dt.as_second());
+        prop_assert_eq!(event.millis.timestamp().as_millisecond(), dt.as_millisecond());
+    }
+
+    #[test]
+    fn serde_duration_round_trip(start in timestamp(), end in timestamp()) {
+        let x = Dtg::from_dt(&start).until(&Dtg::from_dt(&end)).unwrap().to_x();
+        let json = serde_json::to_string(&Duration::from_x(&x).unwrap()).unwrap();
+        prop_assert_eq!(serde_json::from_str::<Duration>(&json).unwrap().to_x(), x);
+    }
+}
+
+#[test]
+fn serde_format_round_trip() {
+    let dtg = Dtg::from("1658448142.5").unwrap();
+    for name in [
+        "all",
+        "bcd",
+        "bcd-clock",
+        "binary-clock",
+        "default",
+        "dtg",
+        "rfc-3339",
+        "x",
+        "x-extended",
+        "%A %-d %B",
+    ] {
+        let fmt = name.parse::<Format>().unwrap();
+        let json = serde_json::to_string(&fmt).unwrap();
+        let decoded = serde_json::from_str::<Format>(&json).unwrap();
+        assert_eq!(
+            dtg.format(&Some(decoded), &None),
+            dtg.format(&Some(fmt), &None),
+        );
+    }
+    assert_eq!(
+        serde_json::to_string(&Format::XExtended).unwrap(),
+        "\"x-extended\""
+    );
+    for (fmt, json) in [
+        (Format::XWith(XAlphabet::dns()), "\"x:dns\""),
+        (Format::XWith(XAlphabet::url_safe()), "\"x:url-safe\""),
+        (
+            Format::XExtendedWith(XAlphabet::new("01").unwrap()),
+            "\"x-extended:01\"",
+        ),
+        (
+            Format::Clock(ClockMode::Binary, ClockGlyphs::Squares),
+            "\"binary-clock:squares\"",
+        ),
+        (Format::custom("literal"), "\"literal\""),
+        (Format::custom("%H:%M"), "\"%H:%M\""),
+        (Format::custom("d"), "\"custom:d\""),
+        (Format::custom("a"), "\"custom:a\""),
+        (Format::custom("x"), "\"custom:x\""),
+        (Format::custom("cd"), "\"custom:cd\""),
+        (Format::custom("x:dns"), "\"custom:x:dns\""),
+        (Format::custom("custom:x"), "\"custom:custom:x\""),
+        (Format::custom("x:%H"), "\"x:%H\""),
+    ] {
+        assert_eq!(serde_json::to_string(&fmt).unwrap(), json);
+        assert_eq!(serde_json::from_str::<Format>(json).unwrap(), fmt);
+    }
+    assert_eq!(
+        serde_json::from_str::<Format>("\"nope\"").unwrap(),
+        Format::custom("nope"),
+    );
+    assert!(serde_json::from_str::<Format>("\"x:0120\"").is_err());
+    assert!(XAlphabet::new("dns").is_err());
+    assert!(serde_json::from_str::<Format>("\"bcd-clock:nope\"").is_err());
+}
+
+#[test]
+fn serde_invalid() {
+    assert!(serde_json::from_str::<Dtg>("\"2022-07-22\"").is_err());
+    assert!(serde_json::from_str::<Dtg>("1658448142").is_err());
+    assert!(serde_json::from_str::<Duration>("\"-0\"").is_err());
+    assert_eq!(
+        serde_json::from_str::<Dtg>("\"2022-07-21T18:02:22-06:00\"").unwrap(),
+        Dtg::from("1658448142").unwrap(),
+    );
+}
@@ -0,0 +1,91 @@
+/*!
+Standard Trait Property Tests
+*/
+
+// # Crates
+
+mod common;
+
+use {
+    common::timestamp,
+    dtg_lib::{ClockGlyphs, ClockMode, Dtg, Format, Timestamp, XAlphabet},
+    proptest::prelude::*,
+    std::{
+        collections::{BTreeSet, HashSet},
+        time::{SystemTime, UNIX_EPOCH},
+    },
+};
+
+// # Tests
+
+proptest! {
+    #[test]
+    fn dtg_display_from_str_round_trip(dt in timestamp()) {
+        let dtg = Dtg::from_dt(&dt);
+        prop_assert_eq!(dtg.to_string().parse::<Dtg>().unwrap(), dtg);
+    }
+
+    #[test]
+    fn dtg_timestamp_round_trip(dt in timestamp()) {
+        let dtg: Dtg = dt.into();
+        prop_assert_eq!(Timestamp::from(dtg), dt);
+    }
+
+    #[test]
+    fn dtg_system_time_round_trip(dt in timestamp()) {
+        let dtg = Dtg::from_dt(&dt);
+        let t: Dtg = SystemTime::try_from(dtg).unwrap().into();
+        prop_assert_eq!(t, dtg);
+    }
+
+    #[test]
+    fn dtg_ord_hash(a in timestamp(), b in timestamp()) {
+        let (x, y) = (Dtg::from_dt(&a), Dtg::from_dt(&b));
+        let copy = x;
+        prop_assert_eq!(copy, x);
+        prop_assert_eq!(x.cmp(&y), a.cmp(&b));
+        prop_assert_eq!(HashSet::from([x, y, copy]).len(), if a == b { 1 } else { 2 });
+        let sorted = BTreeSet::from([x, y]).into_iter().collect::<Vec<Dtg>>();
+        prop_assert_eq!(sorted.first(), Some(&x.min(y)));
+    }
+
+    #[test]
+    fn duration_clone(start in timestamp(), end in timestamp()) {
+        let duration = Dtg::from_dt(&start).until(&Dtg::from_dt(&end)).unwrap();
+        let clone = duration.clone();
+        prop_assert_eq!(clone.to_x(), duration.to_x());
+        prop_assert_eq!(format!("{clone:?}"), format!("{duration:?}"));
+    }
+}
+
+#[test]
+fn format_debug_partial_eq() {
+    for name in ["all", "bcd", "binary-clock", "dtg", "rfc-3339", "x", "%A"] {
+        let fmt = name.parse::<Format>().unwrap();
+        assert_eq!(fmt.clone(), fmt);
+        assert_eq!(format!("{:?}", fmt.clone()), format!("{fmt:?}"));
+    }
+    assert_eq!(Format::custom("%F"), Format::Custom(String::from("%F")));
+    assert_eq!(Format::X, Format::XWith(XAlphabet::default()));
+    assert_eq!(
+        Format::XExtendedWith(XAlphabet::standard()),
+        Format::XExtended
+    );
+    assert_eq!("x".parse::<Format>().unwrap(), Format::X);
+    assert_ne!(Format::X, Format::XExtended);
+    assert_ne!(Format::X, Format::XWith(XAlphabet::url_safe()));
+    assert_ne!(
+        Format::Clock(ClockMode::Bcd, ClockGlyphs::Circles),
+        Format::Clock(ClockMode::Bcd, ClockGlyphs::Squares),
+    );
+    assert_eq!(format!("{:?}", Format::custom("%F")), "Custom(\"%F\")");
+}
+
+#[test]
+fn dtg_invalid() {
+    assert_eq!("2022-07-22".parse::<Dtg>().unwrap_err().code(), 101);
+    let max: Dtg = Timestamp::MAX.into();
+    let far: Dtg = (UNIX_EPOCH + std::time::Duration::from_secs(1 << 40)).into();
+    assert_eq!(far, max);
+    assert_eq!(far.to_string(), "9999-12-30T22:00:00.999999999Z");
+}
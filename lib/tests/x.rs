@@ -0,0 +1,184 @@
+/*!
+"x" Format Property Tests
+*/
+
+// # Crates
+
+mod common;
+
+use {
+    common::timestamp,
+    dtg_lib::{Dtg, DtgErrorKind, Duration, Format, Offset, TimeZone, Timestamp, XAlphabet},
+    proptest::prelude::*,
+};
+
+// # Helper functions
+
+/// Any valid fixed offset timezone
+fn timezone() -> impl Strategy<Value = TimeZone> {
+    (-93_599..=93_599_i32).prop_map(|s| TimeZone::fixed(Offset::from_seconds(s).unwrap()))
+}
+
+/// Built-in and a few custom alphabets
+fn alphabet() -> impl Strategy<Value = XAlphabet> {
+    prop_oneof![
+        Just(XAlphabet::standard()),
+        Just(XAlphabet::url_safe()),
+        Just(XAlphabet::dns()),
+        Just(XAlphabet::new("01").unwrap()),
+        Just(XAlphabet::new("0123456789").unwrap()),
+        Just(XAlphabet::new("zyxwvutsrqponmlkjihgfedcba").unwrap()),
+    ]
+}
+
+// # Tests
+
+proptest! {
+    #[test]
+    fn x_round_trip(s in Timestamp::MIN.as_second()..=Timestamp::MAX.as_second()) {
+        let dtg = Dtg::from_dt(&Timestamp::new(s, 0).unwrap());
+        let x = dtg.x_format();
+        prop_assert_eq!(Dtg::from_x(&x).unwrap(), dtg);
+    }
+
+    #[test]
+    fn x_extended_round_trip(dt in timestamp(), tz in timezone()) {
+        let dtg = Dtg::from_dt(&dt);
+        let x = dtg.format(&Some(Format::XExtended), &Some(tz.clone()));
+        let decoded = Dtg::from_x(&x).unwrap();
+        prop_assert_eq!(&decoded, &dtg);
+        prop_assert_eq!(decoded.format(&Some(Format::XExtended), &Some(tz)), x);
+    }
+
+    #[test]
+    fn x_alphabet_round_trip(dt in timestamp(), tz in timezone(), alphabet in alphabet()) {
+        let dtg = Dtg::from_dt(&dt);
+        let fmt = Some(Format::XExtendedWith(alphabet.clone()));
+        let x = dtg.format(&fmt, &Some(tz.clone()));
+        let decoded = Dtg::from_x_with(&x, &alphabet).unwrap();
+        prop_assert_eq!(&decoded, &dtg);
+        prop_assert_eq!(decoded.format(&fmt, &Some(tz)), x);
+    }
+
+    #[test]
+    fn x_alphabet_dns_case_insensitive(dt in timestamp()) {
+        let dtg = Dtg::from_dt(&dt);
+        let x = dtg.format(&Some(Format::XExtendedWith(XAlphabet::dns())), &None);
+        prop_assert_eq!(Dtg::from_x_with(&x.to_uppercase(), &XAlphabet::dns()).unwrap(), dtg);
+    }
+
+    #[test]
+    fn x_duration_round_trip(start in timestamp(), end in timestamp(), alphabet in alphabet()) {
+        let (start, end) = (Dtg::from_dt(&start), Dtg::from_dt(&end));
+        let x = start.until(&end).unwrap().to_x_with(&alphabet);
+        prop_assert_eq!(Duration::from_x_with(&x, &alphabet).unwrap().to_x_with(&alphabet), x);
+    }
+
+    #[test]
+    fn x_interval_round_trip(start in timestamp(), end in timestamp()) {
+        let (start, end) = (Dtg::from_dt(&start), Dtg::from_dt(&end));
+        let (x_start, x_end) = (start.x_extended_format(&None), end.x_extended_format(&None));
+        let duration = start.until(&end).unwrap().to_x();
+        if !duration.starts_with('-') {
+            let interval = Dtg::from_x_interval(&format!("{x_start}/+{duration}")).unwrap();
+            prop_assert_eq!(&interval.0, &start);
+            prop_assert_eq!(&interval.1, &end);
+        }
+        let interval = Dtg::from_x_interval(&format!("{x_start}/{x_end}")).unwrap();
+        prop_assert_eq!(interval, (start, end));
+    }
+
+    #[test]
+    fn x_extended_utc_whole_seconds_is_x(
+        s in Timestamp::MIN.as_second()..=Timestamp::MAX.as_second(),
+    ) {
+        let dtg = Dtg::from_dt(&Timestamp::new(s, 0).unwrap());
+        prop_assert_eq!(dtg.x_extended_format(&None), dtg.x_format());
+    }
+
+    #[test]
+    fn x_extended_rejects_trailing_zero(dt in timestamp()) {
+        let x = Dtg::from_dt(&dt).x_extended_format(&None);
+        if x.contains('.') {
+            let padded = format!("{x}0");
+            prop_assert!(Dtg::from_x(&padded).is_err());
+        }
+    }
+}
+
+#[test]
+fn x_extended_invalid() {
+    for i in [
+        "Xg6L02M.",
+        "Xg6L02M.U0",
+        "Xg6L02M.1",
+        "Xg6L02M.UUUUUUUUUU",
+        "Xg6L02M+",
+        "Xg6L02M+5",
+        "Xg6L02M+00",
+        "Xg6L02M+5U0",
+        "Xg6L02M+5U11",
+        "-0016000",
+        "11Xg6L02M",
+    ] {
+        assert!(Dtg::from_x(i).is_err(), "{i}");
+    }
+}
+
+#[test]
+fn x_duration_invalid() {
+    for i in ["", "-", "-0", "00", "1.", "1.U0", "1+1", "/", "1U0/"] {
+        assert!(Duration::from_x(i).is_err(), "{i}");
+    }
+    for i in ["Xg6L02M", "Xg6L02M/", "Xg6L02M/+", "Xg6L02M/1U0"] {
+        assert!(Dtg::from_x_interval(i).is_err(), "{i}");
+    }
+}
+
+#[test]
+fn x_alphabet_invalid() {
+    for i in [
+        "",
+        "0",
+        "00",
+        "0-",
+        "0+",
+        "0.",
+        "0/",
+        "0:",
+        "0%",
+        "0 ",
+        "0\t",
+        &"0".repeat(65),
+    ] {
+        assert_eq!(XAlphabet::new(i).unwrap_err().code(), 109, "{i:?}");
+    }
+    for c in ['-', '+', '.', '/', ':', '%'] {
+        assert_eq!(
+            XAlphabet::new(&format!("01{c}")).unwrap_err().to_string(),
+            format!("Invalid \"x\" format alphabet: invalid `{c}`"),
+        );
+    }
+}
+
+#[test]
+fn x_invalid_char_position() {
+    for (i, component, position) in [
+        ("Xg6L0?M", "minute", 5),
+        ("-Xg6L0?M", "minute", 6),
+        ("Xg6L02M.?", "fraction", 8),
+        ("Xg6L02M+0?", "offset", 9),
+        ("Xg6L02M.U+0?", "offset", 11),
+    ] {
+        assert_eq!(
+            Dtg::from_x(i).unwrap_err().kind(),
+            &DtgErrorKind::InvalidXChar {
+                input: i.to_string(),
+                component,
+                position,
+            },
+            "{i}",
+        );
+    }
+    assert_eq!(Duration::from_x("1.?").unwrap_err().code(), 105);
+}